@@ -0,0 +1,59 @@
+// Draws a ring as a glowing orbit line, with a trail behind each cycling electron.
+// The uniform mirrors `RingUniform` in `src/game/ring_material.rs`.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct RingUniform {
+    fill: vec4<f32>,
+    glow: vec4<f32>,
+    radius: f32,
+    brightness: f32,
+    pulse: f32,
+    electron_count: u32,
+    // Two electrons to a vector: their angle, then how far their trail reaches behind them.
+    electrons: array<vec4<f32>, 4>,
+}
+
+@group(2) @binding(0) var<uniform> ring: RingUniform;
+
+const TAU: f32 = 6.283185307;
+// In pixels.
+const LINE_WIDTH: f32 = 2.0;
+const GLOW_WIDTH: f32 = 6.0;
+const TRAIL_WIDTH: f32 = 5.0;
+// How quickly a trail fades behind its electron, per radian.
+const TRAIL_FALLOFF: f32 = 2.5;
+
+fn electron_at(index: u32) -> vec2<f32> {
+    let pair = ring.electrons[index / 2u];
+    return select(pair.xy, pair.zw, index % 2u == 1u);
+}
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    // The circle mesh's UVs span its bounding square, with v pointing down.
+    let position = vec2(mesh.uv.x * 2.0 - 1.0, 1.0 - mesh.uv.y * 2.0) * ring.radius;
+    let distance = length(position);
+    // The orbit runs just inside the edge of the mesh, so none of the line is cut off.
+    let orbit = abs(distance - (ring.radius - LINE_WIDTH * 1.5));
+    let smoothing = max(fwidth(distance), 0.0001);
+    let line = 1.0 - smoothstep(LINE_WIDTH * 0.5 - smoothing, LINE_WIDTH * 0.5 + smoothing, orbit);
+    let halo = exp(-orbit / GLOW_WIDTH);
+
+    let angle = atan2(position.y, position.x);
+    var trail = 0.0;
+    for (var i = 0u; i < ring.electron_count; i += 1u) {
+        let electron = electron_at(i);
+        // How far this point is behind the electron, going round the way it revolves.
+        let behind = fract((electron.x - angle) / TAU) * TAU;
+        if behind < electron.y {
+            trail = max(trail, exp(-behind * TRAIL_FALLOFF));
+        }
+    }
+    trail *= exp(-orbit / TRAIL_WIDTH);
+
+    let strength = (0.4 + 0.6 * ring.brightness) * (1.0 + ring.pulse);
+    let glow = clamp(line * 0.8 * strength + halo * 0.35 * strength + trail, 0.0, 1.0);
+    let fill = ring.fill.a * (1.0 + ring.pulse * 0.5);
+    return vec4(mix(ring.fill.rgb, ring.glow.rgb, glow), max(fill, glow * ring.glow.a));
+}
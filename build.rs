@@ -0,0 +1,55 @@
+//! Lists the files in `assets/` for the `embedded_assets` feature to build into the executable.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Files in `assets/` the game never loads, like the source art, which aren't worth embedding.
+const SKIPPED: &[&str] = &["atom_screenshot.png"];
+const SKIPPED_EXTENSIONS: &[&str] = &["gpl", "svg"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=assets");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_ASSETS").is_none() {
+        return;
+    }
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("assets");
+    let mut files = Vec::new();
+    collect_files(&root, &mut files);
+    files.sort();
+
+    let mut list = String::from("/// Every asset, by its path in `assets/`.\n");
+    list += "const ASSETS: &[(&str, &[u8])] = &[\n";
+    for file in files {
+        let path = file.strip_prefix(&root).unwrap().to_string_lossy();
+        let path = path.replace('\\', "/");
+        if SKIPPED.contains(&path.as_str()) {
+            continue;
+        }
+        list += &format!(
+            "    ({path:?}, include_bytes!({:?})),\n",
+            file.to_string_lossy()
+        );
+    }
+    list += "];\n";
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_assets.rs");
+    fs::write(out, list).unwrap();
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if !path
+            .extension()
+            .is_some_and(|extension| SKIPPED_EXTENSIONS.iter().any(|skip| extension == *skip))
+        {
+            files.push(path);
+        }
+    }
+}
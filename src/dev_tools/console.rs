@@ -0,0 +1,406 @@
+//! An in-game debug console for cheats, opened with the console binding.
+//!
+//! Type a command and press Enter to run it. Up and Down walk through earlier commands,
+//! and Tab completes command names. Type `help` for the list of commands.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+    ui::UiSystem,
+};
+
+use crate::{
+    game::{
+        cycles::CycleCount,
+        movement::Revolve,
+        spawn::atom::{AddProton, AddProtonNeutron, Atom, Proton, Ring},
+        upgrades::{
+            BuyElectron, BuyNextRing, Upgrade, INITIAL_REVOLVE_SPEED, SPEED_UPGRADE_INCREMENT,
+        },
+    },
+    input::{ActionInput, InputAction, InputBinding, InputBindings},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Console>();
+    app.add_systems(Startup, spawn_console);
+    // Read typing before anything else sees the keys, so typing doesn't also play the game.
+    app.add_systems(
+        PreUpdate,
+        (toggle_console, read_console_input)
+            .chain()
+            .after(InputSystem)
+            .before(UiSystem::Focus),
+    );
+    app.add_systems(
+        Update,
+        update_console_ui.run_if(resource_changed::<Console>),
+    );
+}
+
+/// How many lines of output are kept.
+const OUTPUT_LEN: usize = 100;
+/// How many lines of output are shown.
+const VISIBLE_OUTPUT_LINES: usize = 12;
+const FONT_SIZE: f32 = 16.;
+
+type CommandResult = Result<String, String>;
+
+struct ConsoleCommand {
+    /// The words that select the command.
+    name: &'static str,
+    /// Describes the arguments in `help`.
+    args: &'static str,
+    run: fn(&mut World, &[&str]) -> CommandResult,
+}
+
+const COMMANDS: &[ConsoleCommand] = &[
+    ConsoleCommand {
+        name: "help",
+        args: "",
+        run: help,
+    },
+    ConsoleCommand {
+        name: "cycles add",
+        args: "<amount>",
+        run: cycles_add,
+    },
+    ConsoleCommand {
+        name: "ring buy",
+        args: "[count]",
+        run: ring_buy,
+    },
+    ConsoleCommand {
+        name: "electron fill",
+        args: "<ring>",
+        run: electron_fill,
+    },
+    ConsoleCommand {
+        name: "speed set",
+        args: "<ring> <level>",
+        run: speed_set,
+    },
+    ConsoleCommand {
+        name: "element goto",
+        args: "<protons>",
+        run: element_goto,
+    },
+    ConsoleCommand {
+        name: "timescale",
+        args: "<scale>",
+        run: timescale,
+    },
+];
+
+#[derive(Resource, Debug, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    output: VecDeque<String>,
+    /// Commands run so far, oldest first.
+    history: Vec<String>,
+    /// The history entry being shown while walking through history.
+    history_cursor: Option<usize>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        if self.output.len() == OUTPUT_LEN {
+            self.output.pop_front();
+        }
+        self.output.push_back(line.into());
+    }
+
+    fn history_step(&mut self, back: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let cursor = match (self.history_cursor, back) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(cursor), true) => Some(cursor.saturating_sub(1)),
+            (Some(cursor), false) => Some(cursor + 1).filter(|next| *next < self.history.len()),
+        };
+        self.history_cursor = cursor;
+        self.input = cursor
+            .map(|cursor| self.history[cursor].clone())
+            .unwrap_or_default();
+    }
+
+    /// Completes the input to the longest prefix shared by the commands that match it.
+    fn autocomplete(&mut self) {
+        let input = self.input.trim_start().to_string();
+        let matches: Vec<_> = COMMANDS
+            .iter()
+            .map(|command| command.name)
+            .filter(|name| name.starts_with(&input))
+            .collect();
+        match matches.as_slice() {
+            [] => (),
+            [name] => self.input = format!("{name} "),
+            [first, rest @ ..] => {
+                let mut prefix = first.to_string();
+                for name in rest {
+                    while !name.starts_with(&prefix) {
+                        prefix.pop();
+                    }
+                }
+                self.input = prefix;
+                self.print(matches.join("  "));
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn spawn_console(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Debug Console"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    padding: UiRect::all(Val::Px(8.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.85)),
+                z_index: ZIndex::Global(200),
+                ..default()
+            },
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                ConsoleText,
+            ));
+        });
+}
+
+fn toggle_console(input: ActionInput, mut console: ResMut<Console>) {
+    if input.just_pressed(InputAction::ToggleConsole) {
+        console.open = !console.open;
+    }
+}
+
+fn read_console_input(
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+) {
+    if !console.open {
+        keyboard_events.clear();
+        return;
+    }
+
+    let toggle_bindings = bindings.get(InputAction::ToggleConsole);
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed
+            || toggle_bindings.contains(&InputBinding::Key(event.key_code))
+        {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                console.history_cursor = None;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                console.print(format!("> {line}"));
+                if console.history.last() != Some(&line) {
+                    console.history.push(line.clone());
+                }
+                commands.add(move |world: &mut World| run_command(world, &line));
+            }
+            Key::Escape => console.open = false,
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Tab => console.autocomplete(),
+            Key::ArrowUp => console.history_step(true),
+            Key::ArrowDown => console.history_step(false),
+            Key::Space => console.input.push(' '),
+            Key::Character(text) => console.input.push_str(text),
+            _ => (),
+        }
+    }
+
+    // Keep the keys typed into the console from reaching the game.
+    keys.reset_all();
+}
+
+fn update_console_ui(
+    console: Res<Console>,
+    mut query_root: Query<&mut Style, With<ConsoleRoot>>,
+    mut query_text: Query<&mut Text, With<ConsoleText>>,
+) {
+    for mut style in &mut query_root {
+        style.display = if console.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for mut text in &mut query_text {
+        let skip = console.output.len().saturating_sub(VISIBLE_OUTPUT_LINES);
+        let mut value = String::new();
+        for line in console.output.iter().skip(skip) {
+            value += line;
+            value += "\n";
+        }
+        value += &format!("> {}_", console.input);
+        text.sections[0].value = value;
+    }
+}
+
+fn run_command(world: &mut World, line: &str) {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let command = COMMANDS.iter().find(|command| {
+        let name: Vec<&str> = command.name.split(' ').collect();
+        words.starts_with(&name)
+    });
+    let result = match command {
+        Some(command) => {
+            let args = &words[command.name.split(' ').count()..];
+            (command.run)(world, args)
+        }
+        None => Err(format!("Unknown command {line:?}, try \"help\"")),
+    };
+    let output = match result {
+        Ok(output) => output,
+        Err(error) => format!("Error: {error}"),
+    };
+    log::info!("Console: {output}");
+    let mut console = world.resource_mut::<Console>();
+    for line in output.lines() {
+        console.print(line);
+    }
+}
+
+/// Parses the argument at `index`, naming it `name` in errors.
+fn arg<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let arg = args.get(index).ok_or(format!("Missing {name}"))?;
+    arg.parse().map_err(|_| format!("Invalid {name} {arg:?}"))
+}
+
+fn find_atom(world: &mut World) -> Result<Entity, String> {
+    world
+        .query_filtered::<Entity, With<Atom>>()
+        .get_single(world)
+        .map_err(|_| "There is no atom, start a game first".to_string())
+}
+
+/// Finds a ring by its number, counting from one as the UI does.
+fn find_ring(world: &mut World, number: usize) -> Result<Entity, String> {
+    world
+        .query::<(Entity, &Ring)>()
+        .iter(world)
+        .find(|(_, ring)| ring.index + 1 == number)
+        .map(|(entity, _)| entity)
+        .ok_or(format!("There is no ring {number}"))
+}
+
+fn help(_world: &mut World, _args: &[&str]) -> CommandResult {
+    let lines: Vec<_> = COMMANDS
+        .iter()
+        .map(|command| format!("{} {}", command.name, command.args))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn cycles_add(world: &mut World, args: &[&str]) -> CommandResult {
+    let amount: u32 = arg(args, 0, "amount")?;
+    let mut count = world.resource_mut::<CycleCount>();
+    count.0 = count.0.saturating_add(amount);
+    Ok(format!("Cycles: {}", count.0))
+}
+
+fn ring_buy(world: &mut World, args: &[&str]) -> CommandResult {
+    let count: u32 = if args.is_empty() {
+        1
+    } else {
+        arg(args, 0, "count")?
+    };
+    let atom = find_atom(world)?;
+    let levels = count.min(BuyNextRing.remaining(world, atom).unwrap_or(count));
+    if levels == 0 {
+        return Err("Every ring has been bought".to_string());
+    }
+    BuyNextRing.apply(world, atom, levels);
+    Ok(format!("Bought {levels} ring(s)"))
+}
+
+fn electron_fill(world: &mut World, args: &[&str]) -> CommandResult {
+    let number: usize = arg(args, 0, "ring")?;
+    let ring = find_ring(world, number)?;
+    let levels = BuyElectron.remaining(world, ring).unwrap_or_default();
+    BuyElectron.apply(world, ring, levels);
+    Ok(format!("Added {levels} electron(s) to ring {number}"))
+}
+
+fn speed_set(world: &mut World, args: &[&str]) -> CommandResult {
+    let number: usize = arg(args, 0, "ring")?;
+    let level: u32 = arg(args, 1, "level")?;
+    let ring = find_ring(world, number)?;
+    let mut revolve = world
+        .get_mut::<Revolve>(ring)
+        .ok_or(format!("Ring {number} doesn't revolve"))?;
+    revolve.level = level;
+    revolve.speed = INITIAL_REVOLVE_SPEED + SPEED_UPGRADE_INCREMENT * level as f32;
+    Ok(format!("Ring {number} speed: {:.2}", revolve.speed))
+}
+
+fn element_goto(world: &mut World, args: &[&str]) -> CommandResult {
+    let target: usize = arg(args, 0, "protons")?;
+    find_atom(world)?;
+    let protons = world
+        .query_filtered::<(), With<Proton>>()
+        .iter(world)
+        .count();
+    if target < protons {
+        return Err(format!("The atom already has {protons} protons"));
+    }
+    for proton in protons..target {
+        // The first proton comes without a neutron, as it does when buying electrons.
+        if proton == 0 {
+            world.trigger(AddProton);
+        } else {
+            world.trigger(AddProtonNeutron);
+        }
+    }
+    Ok(format!("The atom has {target} protons"))
+}
+
+fn timescale(world: &mut World, args: &[&str]) -> CommandResult {
+    let scale: f32 = arg(args, 0, "scale")?;
+    if scale <= 0. || !scale.is_finite() {
+        return Err("The scale must be above zero".to_string());
+    }
+    world
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(scale);
+    Ok(format!("Time scale: {scale}"))
+}
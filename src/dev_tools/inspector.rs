@@ -0,0 +1,792 @@
+//! A live inspector for the game's entities and resources, opened with the inspector binding.
+//!
+//! Everything is found through the type registry, so any component or resource from this crate
+//! that is registered with `#[reflect(Component)]` or `#[reflect(Resource)]` shows up.
+//! Numeric fields can be nudged with the `-` and `+` buttons, ten times as far with Shift held,
+//! or clicked to type in a new value and set it with Enter.
+
+use std::{any::TypeId, time::Duration};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        mouse::{MouseScrollUnit, MouseWheel},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+    reflect::{GetPath, ReflectRef},
+    time::common_conditions::on_timer,
+    ui::UiSystem,
+    utils::HashSet,
+};
+
+use crate::{
+    game::spawn::atom::Atom,
+    input::{ActionInput, InputAction},
+    ui::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Inspector>();
+    app.add_systems(Startup, spawn_inspector);
+    // Read typing before anything else sees the keys, like the console does.
+    app.add_systems(
+        PreUpdate,
+        read_field_input.after(InputSystem).before(UiSystem::Focus),
+    );
+    app.add_systems(
+        Update,
+        (
+            toggle_inspector,
+            (handle_inspector_buttons, scroll_inspector),
+            collect_rows.run_if(inspector_open.and_then(
+                on_timer(Duration::from_millis(REFRESH_MILLIS)).or_else(inspector_changed),
+            )),
+            show_rows.run_if(inspector_changed),
+        )
+            .chain(),
+    );
+}
+
+const REFRESH_MILLIS: u64 = 250;
+const PANEL_WIDTH: f32 = 380.;
+const INDENT: f32 = 14.;
+const FONT_SIZE: f32 = 14.;
+/// How deep into nested values fields are listed.
+const MAX_FIELD_DEPTH: usize = 4;
+/// Longer values are cut short.
+const MAX_VALUE_LEN: usize = 32;
+/// Only types from this crate are inspected, to keep Bevy's own components out of the way.
+const CRATE_PREFIX: &str = concat!(env!("CARGO_CRATE_NAME"), "::");
+
+/// Something that can be expanded to show what it contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ExpandKey {
+    Entity(Entity),
+    Resource(TypeId),
+}
+
+/// Where an inspected value lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldOwner {
+    Resource,
+    Component(Entity),
+}
+
+/// A numeric field that can be edited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldRef {
+    owner: FieldOwner,
+    /// The resource or component type holding the field.
+    type_id: TypeId,
+    /// The field's reflection path within that type.
+    path: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct InspectorRow {
+    depth: usize,
+    label: String,
+    value: Option<String>,
+    /// Clicking the row expands or collapses this.
+    toggle: Option<ExpandKey>,
+    field: Option<FieldRef>,
+}
+
+impl InspectorRow {
+    fn new(depth: usize, label: impl Into<String>) -> Self {
+        Self {
+            depth,
+            label: label.into(),
+            value: None,
+            toggle: None,
+            field: None,
+        }
+    }
+
+    /// Whether the row needs rebuilding rather than just a new value.
+    fn same_layout(&self, other: &Self) -> bool {
+        self.depth == other.depth
+            && self.label == other.label
+            && self.toggle == other.toggle
+            && self.field == other.field
+    }
+}
+
+/// A value being typed into a numeric field.
+#[derive(Debug, Clone)]
+struct FieldEdit {
+    /// The index of the field's row.
+    row: usize,
+    field: FieldRef,
+    text: String,
+}
+
+#[derive(Resource, Debug, Default)]
+struct Inspector {
+    open: bool,
+    expanded: HashSet<ExpandKey>,
+    rows: Vec<InspectorRow>,
+    /// Set when the rows changed in a way that needs the panel rebuilt.
+    rebuild: bool,
+    editing: Option<FieldEdit>,
+}
+
+#[derive(Component)]
+struct InspectorPanel;
+
+/// The column of rows, moved up and down to scroll.
+#[derive(Component, Default)]
+struct InspectorList {
+    position: f32,
+}
+
+/// The value text of the row at this index.
+#[derive(Component)]
+struct InspectorValue(usize);
+
+#[derive(Component, Debug, Clone)]
+enum InspectorButton {
+    Toggle(ExpandKey),
+    /// Changes a numeric field by one step in this direction.
+    Nudge(FieldRef, f64),
+    /// Starts typing a new value for the numeric field in this row.
+    Edit(usize, FieldRef),
+}
+
+fn inspector_open(inspector: Res<Inspector>) -> bool {
+    inspector.open
+}
+
+fn inspector_changed(inspector: Res<Inspector>) -> bool {
+    inspector.is_changed()
+}
+
+fn spawn_inspector(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Inspector"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.),
+                    left: Val::Px(5.),
+                    width: Val::Px(PANEL_WIDTH),
+                    max_height: Val::Percent(90.),
+                    padding: UiRect::all(Val::Px(6.)),
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.85)),
+                z_index: ZIndex::Global(150),
+                ..default()
+            },
+            Interaction::default(),
+            InspectorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.),
+                        row_gap: Val::Px(1.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                InspectorList::default(),
+            ));
+        });
+}
+
+fn toggle_inspector(
+    input: ActionInput,
+    mut inspector: ResMut<Inspector>,
+    mut query_panel: Query<&mut Style, With<InspectorPanel>>,
+) {
+    if !input.just_pressed(InputAction::ToggleInspector) {
+        return;
+    }
+    inspector.open = !inspector.open;
+    inspector.editing = None;
+    for mut style in &mut query_panel {
+        style.display = if inspector.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn scroll_inspector(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    query_panel: Query<(&Interaction, &Node), With<InspectorPanel>>,
+    mut query_list: Query<(&mut InspectorList, &mut Style, &Node)>,
+) {
+    let Ok((interaction, panel_node)) = query_panel.get_single() else {
+        return;
+    };
+    if *interaction == Interaction::None {
+        mouse_wheel_events.clear();
+        return;
+    }
+    for event in mouse_wheel_events.read() {
+        for (mut list, mut style, list_node) in &mut query_list {
+            let max_scroll = (list_node.size().y - panel_node.size().y).max(0.);
+            let dy = match event.unit {
+                MouseScrollUnit::Line => event.y * 20.,
+                MouseScrollUnit::Pixel => event.y,
+            };
+            list.position = (list.position + dy).clamp(-max_scroll, 0.);
+            style.top = Val::Px(list.position);
+        }
+    }
+}
+
+fn handle_inspector_buttons(
+    mut commands: Commands,
+    mut inspector: ResMut<Inspector>,
+    keys: Res<ButtonInput<KeyCode>>,
+    query_button: InteractionQuery<&InspectorButton>,
+) {
+    let scale = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        10.
+    } else {
+        1.
+    };
+    for (interaction, button) in &query_button {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        match button {
+            InspectorButton::Toggle(key) => {
+                if !inspector.expanded.remove(key) {
+                    inspector.expanded.insert(*key);
+                }
+            }
+            InspectorButton::Nudge(field, delta) => {
+                let field = field.clone();
+                let delta = delta * scale;
+                commands.add(move |world: &mut World| nudge_field(world, &field, delta));
+                inspector.set_changed();
+            }
+            InspectorButton::Edit(row, field) => {
+                inspector.editing = Some(FieldEdit {
+                    row: *row,
+                    field: field.clone(),
+                    text: String::new(),
+                });
+            }
+        }
+    }
+}
+
+/// Types into the field being edited. Enter sets the value, Escape leaves it as it was.
+fn read_field_input(
+    mut commands: Commands,
+    mut inspector: ResMut<Inspector>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+) {
+    let Some(edit) = inspector.editing.as_mut() else {
+        keyboard_events.clear();
+        return;
+    };
+    let mut done = false;
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let FieldEdit { field, text, .. } = edit.clone();
+                commands.add(move |world: &mut World| set_field(world, &field, &text));
+                done = true;
+            }
+            Key::Escape => done = true,
+            Key::Backspace => {
+                edit.text.pop();
+            }
+            Key::Character(text) => edit.text.push_str(text),
+            _ => (),
+        }
+    }
+    if done {
+        inspector.editing = None;
+    }
+
+    // Keep the keys typed into the field from reaching the game.
+    keys.reset_all();
+}
+
+/// Reads every inspected value into rows.
+fn collect_rows(world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let expanded = world.resource::<Inspector>().expanded.clone();
+    let mut rows = vec![InspectorRow::new(0, "Resources")];
+
+    let mut resources: Vec<_> = registry
+        .iter()
+        .filter(|registration| {
+            registration
+                .type_info()
+                .type_path()
+                .starts_with(CRATE_PREFIX)
+        })
+        .filter_map(|registration| {
+            let reflect = registration.data::<ReflectResource>()?.reflect(world)?;
+            Some((registration.type_info(), reflect))
+        })
+        .collect();
+    resources.sort_by_key(|(info, _)| info.type_path_table().short_path());
+    for (info, value) in resources {
+        let key = ExpandKey::Resource(info.type_id());
+        rows.push(expandable_row(
+            1,
+            info.type_path_table().short_path(),
+            key,
+            &expanded,
+        ));
+        if expanded.contains(&key) {
+            push_fields(&mut rows, value, String::new(), 2, &|path| FieldRef {
+                owner: FieldOwner::Resource,
+                type_id: info.type_id(),
+                path,
+            });
+        }
+    }
+
+    rows.push(InspectorRow::new(0, "Entities"));
+    let atoms: Vec<Entity> = world
+        .query_filtered::<Entity, With<Atom>>()
+        .iter(world)
+        .collect();
+    for atom in atoms {
+        push_entity(&mut rows, world, &registry, &expanded, atom, 1);
+    }
+
+    let mut inspector = world.resource_mut::<Inspector>();
+    let rebuild = inspector.rows.len() != rows.len()
+        || inspector
+            .rows
+            .iter()
+            .zip(&rows)
+            .any(|(old, new)| !old.same_layout(new));
+    if rebuild || inspector.rows != rows {
+        inspector.rows = rows;
+        inspector.rebuild |= rebuild;
+    }
+}
+
+fn expandable_row(
+    depth: usize,
+    label: &str,
+    key: ExpandKey,
+    expanded: &HashSet<ExpandKey>,
+) -> InspectorRow {
+    let marker = if expanded.contains(&key) {
+        "[-]"
+    } else {
+        "[+]"
+    };
+    InspectorRow {
+        toggle: Some(key),
+        ..InspectorRow::new(depth, format!("{marker} {label}"))
+    }
+}
+
+/// The crate components on an entity, as their registration and reflected value.
+fn entity_components<'w>(
+    world: &'w World,
+    registry: &'w bevy::reflect::TypeRegistry,
+    entity: Entity,
+) -> Vec<(&'w bevy::reflect::TypeInfo, &'w dyn Reflect)> {
+    world
+        .inspect_entity(entity)
+        .into_iter()
+        .filter_map(|info| registry.get(info.type_id()?))
+        .filter(|registration| {
+            registration
+                .type_info()
+                .type_path()
+                .starts_with(CRATE_PREFIX)
+        })
+        .filter_map(|registration| {
+            let reflect = registration
+                .data::<ReflectComponent>()?
+                .reflect(world.entity(entity))?;
+            Some((registration.type_info(), reflect))
+        })
+        .collect()
+}
+
+fn push_entity(
+    rows: &mut Vec<InspectorRow>,
+    world: &World,
+    registry: &bevy::reflect::TypeRegistry,
+    expanded: &HashSet<ExpandKey>,
+    entity: Entity,
+    depth: usize,
+) {
+    let components = entity_components(world, registry, entity);
+    let names: Vec<_> = components
+        .iter()
+        .map(|(info, _)| info.type_path_table().short_path())
+        .collect();
+    let label = format!("{} {}", names.join(", "), entity);
+    let key = ExpandKey::Entity(entity);
+    rows.push(expandable_row(depth, &label, key, expanded));
+    if !expanded.contains(&key) {
+        return;
+    }
+
+    for (info, value) in components {
+        if !has_fields(value) {
+            continue;
+        }
+        rows.push(InspectorRow::new(
+            depth + 1,
+            info.type_path_table().short_path(),
+        ));
+        push_fields(rows, value, String::new(), depth + 2, &|path| FieldRef {
+            owner: FieldOwner::Component(entity),
+            type_id: info.type_id(),
+            path,
+        });
+    }
+
+    // Children made only of marker components, like the protons in the nucleus,
+    // are counted instead of listed.
+    let mut markers: Vec<(String, usize)> = Vec::new();
+    for child in world.get::<Children>(entity).into_iter().flatten() {
+        let components = entity_components(world, registry, *child);
+        if components.is_empty() {
+            continue;
+        }
+        if components.iter().any(|(_, value)| has_fields(*value)) {
+            push_entity(rows, world, registry, expanded, *child, depth + 1);
+            continue;
+        }
+        let names: Vec<_> = components
+            .iter()
+            .map(|(info, _)| info.type_path_table().short_path())
+            .collect();
+        let name = names.join(", ");
+        match markers.iter_mut().find(|(marker, _)| *marker == name) {
+            Some((_, count)) => *count += 1,
+            None => markers.push((name, 1)),
+        }
+    }
+    for (name, count) in markers {
+        rows.push(InspectorRow::new(depth + 1, format!("{name} x{count}")));
+    }
+}
+
+fn has_fields(value: &dyn Reflect) -> bool {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.field_len() > 0,
+        ReflectRef::TupleStruct(value) => value.field_len() > 0,
+        _ => true,
+    }
+}
+
+/// Adds a row for every field in `value`, editable if it is a number.
+fn push_fields(
+    rows: &mut Vec<InspectorRow>,
+    value: &dyn Reflect,
+    path: String,
+    depth: usize,
+    field_ref: &dyn Fn(String) -> FieldRef,
+) {
+    let fields: Vec<(String, String, &dyn Reflect)> = match value.reflect_ref() {
+        ReflectRef::Struct(value) => (0..value.field_len())
+            .filter_map(|index| {
+                let name = value.name_at(index)?;
+                Some((
+                    name.to_string(),
+                    format!("{path}.{name}"),
+                    value.field_at(index)?,
+                ))
+            })
+            .collect(),
+        ReflectRef::TupleStruct(value) => (0..value.field_len())
+            .filter_map(|index| {
+                Some((
+                    index.to_string(),
+                    format!("{path}.{index}"),
+                    value.field(index)?,
+                ))
+            })
+            .collect(),
+        ReflectRef::Enum(value) if value.field_len() == 1 => {
+            // Look inside single field variants such as `Some(timer)`.
+            let Some(field) = value.field_at(0) else {
+                return;
+            };
+            let name = value.variant_name().to_string();
+            vec![(name, format!("{path}.0"), field)]
+        }
+        _ => return,
+    };
+
+    for (name, path, field) in fields {
+        if let Some(number) = format_number(field) {
+            rows.push(InspectorRow {
+                value: Some(number),
+                field: Some(field_ref(path)),
+                ..InspectorRow::new(depth, name)
+            });
+        } else if depth < MAX_FIELD_DEPTH
+            && matches!(
+                field.reflect_ref(),
+                ReflectRef::Struct(_) | ReflectRef::TupleStruct(_) | ReflectRef::Enum(_)
+            )
+            && has_fields(field)
+            && !is_unit_enum(field)
+        {
+            rows.push(InspectorRow::new(depth, name));
+            push_fields(rows, field, path, depth + 1, field_ref);
+        } else {
+            let mut value = format!("{field:?}");
+            if value.chars().count() > MAX_VALUE_LEN {
+                value = value.chars().take(MAX_VALUE_LEN).collect::<String>() + "...";
+            }
+            rows.push(InspectorRow {
+                value: Some(value),
+                ..InspectorRow::new(depth, name)
+            });
+        }
+    }
+}
+
+fn is_unit_enum(value: &dyn Reflect) -> bool {
+    matches!(value.reflect_ref(), ReflectRef::Enum(value) if value.field_len() == 0)
+}
+
+macro_rules! numbers {
+    ($value:expr, |$number:ident| $body:expr, $($ty:ty),*) => {
+        $(if let Some($number) = $value.downcast_ref::<$ty>() { return Some($body); })*
+    };
+}
+
+macro_rules! nudge_numbers {
+    ($value:expr, $delta:expr, $($ty:ty),*) => {
+        // Float to integer casts saturate, so integers stop at their bounds.
+        $(if let Some(number) = $value.downcast_mut::<$ty>() {
+            *number = (*number as f64 + $delta) as $ty;
+            return;
+        })*
+    };
+}
+
+fn format_number(value: &dyn Reflect) -> Option<String> {
+    numbers!(value, |number| format!("{number:.3}"), f32, f64);
+    numbers!(
+        value,
+        |number| number.to_string(),
+        u8,
+        u16,
+        u32,
+        u64,
+        usize,
+        i8,
+        i16,
+        i32,
+        i64,
+        isize
+    );
+    None
+}
+
+/// The step for each press of a nudge button.
+fn nudge_step(value: &dyn Reflect) -> f64 {
+    if value.is::<f32>() || value.is::<f64>() {
+        0.1
+    } else {
+        1.
+    }
+}
+
+fn nudge_value(value: &mut dyn Reflect, delta: f64) {
+    nudge_numbers!(value, delta, f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+}
+
+macro_rules! parse_numbers {
+    ($value:expr, $text:expr, $($ty:ty),*) => {
+        $(if $value.is::<$ty>() {
+            return $text.parse::<$ty>().ok().map(|number| Box::new(number) as Box<dyn Reflect>);
+        })*
+    };
+}
+
+/// Parses `text` as a number of the same type as `value`.
+fn parse_number(value: &dyn Reflect, text: &str) -> Option<Box<dyn Reflect>> {
+    let text = text.trim();
+    parse_numbers!(value, text, f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    None
+}
+
+fn nudge_field(world: &mut World, field: &FieldRef, delta: f64) {
+    edit_field(world, field, |number| {
+        let step = nudge_step(number);
+        nudge_value(number, delta * step);
+    });
+}
+
+fn set_field(world: &mut World, field: &FieldRef, text: &str) {
+    edit_field(world, field, |number| match parse_number(number, text) {
+        Some(parsed) => number.apply(parsed.as_ref()),
+        None => log::warn!(
+            "Can't set {} to {text:?}: not a {}",
+            field.path,
+            number.reflect_short_type_path()
+        ),
+    });
+}
+
+/// Runs `edit` on the value `field` refers to.
+fn edit_field(world: &mut World, field: &FieldRef, edit: impl FnOnce(&mut dyn Reflect)) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let value = match field.owner {
+        FieldOwner::Resource => registry
+            .get_type_data::<ReflectResource>(field.type_id)
+            .and_then(|reflect| reflect.reflect_mut(world)),
+        FieldOwner::Component(entity) => {
+            let Some(entity) = world.get_entity_mut(entity) else {
+                return;
+            };
+            registry
+                .get_type_data::<ReflectComponent>(field.type_id)
+                .and_then(|reflect| reflect.reflect_mut(entity))
+        }
+    };
+    let Some(mut value) = value else {
+        return;
+    };
+    match value.reflect_path_mut(field.path.as_str()) {
+        Ok(number) => edit(number),
+        Err(error) => log::warn!("Can't edit {}: {error}", field.path),
+    }
+}
+
+fn show_rows(
+    mut commands: Commands,
+    mut inspector: ResMut<Inspector>,
+    query_list: Query<Entity, With<InspectorList>>,
+    mut query_value: Query<(&InspectorValue, &mut Text)>,
+) {
+    if !inspector.rebuild {
+        for (InspectorValue(index), mut text) in &mut query_value {
+            let value = match &inspector.editing {
+                // A cursor after what has been typed so far.
+                Some(edit) if edit.row == *index => Some(format!("{}_", edit.text)),
+                _ => inspector.rows.get(*index).and_then(|row| row.value.clone()),
+            };
+            if let Some(value) = value.filter(|value| text.sections[0].value != *value) {
+                text.sections[0].value = value;
+            }
+        }
+        return;
+    }
+    let inspector = inspector.bypass_change_detection();
+    inspector.rebuild = false;
+    // Stop editing a field whose row has moved.
+    if let Some(edit) = &inspector.editing {
+        let row = inspector.rows.get(edit.row);
+        if row.and_then(|row| row.field.as_ref()) != Some(&edit.field) {
+            inspector.editing = None;
+        }
+    }
+
+    let Ok(list) = query_list.get_single() else {
+        return;
+    };
+    commands
+        .entity(list)
+        .despawn_descendants()
+        .with_children(|parent| {
+            for (index, row) in inspector.rows.iter().enumerate() {
+                spawn_row(parent, index, row);
+            }
+        });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, index: usize, row: &InspectorRow) {
+    let text_style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+    let mut entity = parent.spawn(NodeBundle {
+        style: Style {
+            padding: UiRect::left(Val::Px(row.depth as f32 * INDENT)),
+            column_gap: Val::Px(6.),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ..default()
+    });
+    if let Some(key) = row.toggle {
+        entity.insert((
+            Interaction::default(),
+            InteractionPalette {
+                none: Color::NONE,
+                hovered: BUTTON_HOVERED_BACKGROUND,
+                pressed: BUTTON_PRESSED_BACKGROUND,
+            },
+            InspectorButton::Toggle(key),
+        ));
+    }
+    entity.with_children(|parent| {
+        let color = if row.depth == 0 {
+            HEADER_TEXT
+        } else {
+            BUTTON_TEXT
+        };
+        parent.spawn(TextBundle::from_section(
+            row.label.clone(),
+            text_style(color),
+        ));
+        if let Some(value) = &row.value {
+            let mut value = parent.spawn((
+                TextBundle::from_section(value.clone(), text_style(LABEL_TEXT)),
+                InspectorValue(index),
+            ));
+            if let Some(field) = &row.field {
+                value.insert((
+                    Interaction::default(),
+                    InspectorButton::Edit(index, field.clone()),
+                ));
+            }
+        }
+        if let Some(field) = &row.field {
+            for (label, sign) in [("-", -1.), ("+", 1.)] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(FONT_SIZE + 4.),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            background_color: BackgroundColor(NODE_BACKGROUND),
+                            ..default()
+                        },
+                        InteractionPalette {
+                            none: NODE_BACKGROUND,
+                            hovered: BUTTON_HOVERED_BACKGROUND,
+                            pressed: BUTTON_PRESSED_BACKGROUND,
+                        },
+                        InspectorButton::Nudge(field.clone(), sign),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(label, text_style(BUTTON_TEXT)));
+                    });
+            }
+        }
+    });
+}
@@ -4,7 +4,12 @@ use bevy::{dev_tools::states::log_transitions, prelude::*};
 
 use crate::screen::Screen;
 
+mod console;
+mod inspector;
+
 pub(super) fn plugin(app: &mut App) {
     // Print state transitions in dev builds
     app.add_systems(Update, log_transitions::<Screen>);
+
+    app.add_plugins((console::plugin, inspector::plugin));
 }
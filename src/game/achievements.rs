@@ -0,0 +1,273 @@
+//! Goals for the player to reach, remembered between sessions.
+//!
+//! Achievements are declared in [`ACHIEVEMENTS`]. Their progress is tracked within a single
+//! game from events like [`AddCycle`] and [`PurchaseCompleted`], but once unlocked they stay
+//! unlocked.
+
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::game::cycles::AddCycle;
+use crate::game::spawn::atom::{AddProton, AddProtonNeutron, Electron, Ring};
+use crate::game::upgrades::{PurchaseCompleted, SpeedUpgrade, Upgrade};
+use crate::persistence::DataDir;
+use crate::screen::{InRun, Screen};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AchievementProgress>();
+    app.init_resource::<AchievementProgress>();
+    let unlocked = UnlockedAchievements::load(app.world().resource::<DataDir>());
+    app.insert_resource(unlocked);
+    app.add_systems(OnEnter(InRun), reset_achievement_progress);
+
+    app.observe(track_cycles)
+        .observe(track_protons)
+        .observe(track_proton_neutrons)
+        .observe(track_purchases)
+        .observe(track_filled_rings);
+    app.add_systems(
+        Update,
+        (
+            track_electron_clicks.in_set(AppSet::RecordInput),
+            unlock_achievements
+                .run_if(resource_changed::<AchievementProgress>)
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// What has to be done to unlock an achievement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementGoal {
+    /// Build an atom with at least this many protons.
+    ReachElement(u32),
+    /// Earn this many cycles.
+    TotalCycles(u64),
+    /// Fill the ring with this index with electrons.
+    FillRing(usize),
+    /// Buy this many speed levels across every ring.
+    SpeedLevels(u32),
+    /// Click electrons this many times.
+    ElectronClicks(u32),
+}
+
+impl AchievementGoal {
+    /// The current and target values of the goal.
+    pub fn progress(&self, progress: &AchievementProgress) -> (u64, u64) {
+        match *self {
+            Self::ReachElement(protons) => (progress.protons.into(), protons.into()),
+            Self::TotalCycles(cycles) => (progress.total_cycles, cycles),
+            Self::FillRing(ring) => (progress.filled_rings.contains(&ring).into(), 1),
+            Self::SpeedLevels(levels) => (progress.speed_levels.into(), levels.into()),
+            Self::ElectronClicks(clicks) => (progress.electron_clicks.into(), clicks.into()),
+        }
+    }
+
+    pub fn is_met(&self, progress: &AchievementProgress) -> bool {
+        let (current, target) = self.progress(progress);
+        current >= target
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Achievement {
+    /// Identifies the achievement in save data, so must not change once released.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub goal: AchievementGoal,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "hydrogen",
+        name: "Spark",
+        description: "Make Hydrogen",
+        goal: AchievementGoal::ReachElement(1),
+    },
+    Achievement {
+        id: "carbon",
+        name: "Organic",
+        description: "Make Carbon",
+        goal: AchievementGoal::ReachElement(6),
+    },
+    Achievement {
+        id: "neon",
+        name: "Noble",
+        description: "Make Neon",
+        goal: AchievementGoal::ReachElement(10),
+    },
+    Achievement {
+        id: "cycles_100",
+        name: "Spinning Up",
+        description: "Earn 100 cycles",
+        goal: AchievementGoal::TotalCycles(100),
+    },
+    Achievement {
+        id: "cycles_10000",
+        name: "Perpetual Motion",
+        description: "Earn 10000 cycles",
+        goal: AchievementGoal::TotalCycles(10_000),
+    },
+    Achievement {
+        id: "fill_first_ring",
+        name: "Full Shell",
+        description: "Fill the first ring",
+        goal: AchievementGoal::FillRing(0),
+    },
+    Achievement {
+        id: "fill_fifth_ring",
+        name: "Outer Limits",
+        description: "Fill the fifth ring",
+        goal: AchievementGoal::FillRing(4),
+    },
+    Achievement {
+        id: "speed_10",
+        name: "Centrifuge",
+        description: "Buy 10 speed levels",
+        goal: AchievementGoal::SpeedLevels(10),
+    },
+    Achievement {
+        id: "clicks_100",
+        name: "Hands On",
+        description: "Click electrons 100 times",
+        goal: AchievementGoal::ElectronClicks(100),
+    },
+];
+
+/// Triggered when an achievement is unlocked for the first time.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AchievementUnlocked(pub &'static Achievement);
+
+/// Progress towards the achievements within the current game.
+#[derive(Resource, Debug, Default, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AchievementProgress {
+    pub protons: u32,
+    pub total_cycles: u64,
+    /// Indices of every ring that has been filled with electrons.
+    pub filled_rings: Vec<usize>,
+    pub speed_levels: u32,
+    pub electron_clicks: u32,
+}
+
+/// The ids of every unlocked achievement, in the order they were unlocked.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct UnlockedAchievements(Vec<&'static str>);
+
+impl UnlockedAchievements {
+    const SAVE_KEY: &'static str = "achievements";
+
+    fn load(data_dir: &DataDir) -> Self {
+        let saved = data_dir.load(Self::SAVE_KEY).unwrap_or_default();
+        Self(
+            saved
+                .lines()
+                .filter_map(|id| ACHIEVEMENTS.iter().find(|achievement| achievement.id == id))
+                .map(|achievement| achievement.id)
+                .collect(),
+        )
+    }
+
+    fn save(&self, data_dir: &DataDir) {
+        data_dir.save(Self::SAVE_KEY, &self.0.join("\n"));
+    }
+
+    pub fn contains(&self, achievement: &Achievement) -> bool {
+        self.0.contains(&achievement.id)
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn reset_achievement_progress(mut progress: ResMut<AchievementProgress>) {
+    *progress = AchievementProgress::default();
+}
+
+fn track_cycles(_trigger: Trigger<AddCycle>, mut progress: ResMut<AchievementProgress>) {
+    progress.total_cycles += 1;
+}
+
+fn track_protons(_trigger: Trigger<AddProton>, mut progress: ResMut<AchievementProgress>) {
+    progress.protons += 1;
+}
+
+fn track_proton_neutrons(
+    _trigger: Trigger<AddProtonNeutron>,
+    mut progress: ResMut<AchievementProgress>,
+) {
+    progress.protons += 1;
+}
+
+fn track_purchases(trigger: Trigger<PurchaseCompleted>, mut progress: ResMut<AchievementProgress>) {
+    let event = trigger.event();
+    if event.upgrade == SpeedUpgrade.id() {
+        progress.speed_levels += event.levels;
+    }
+}
+
+/// Checks whether each new electron fills its ring, however it was added.
+fn track_filled_rings(
+    trigger: Trigger<OnAdd, Electron>,
+    mut progress: ResMut<AchievementProgress>,
+    query_parent: Query<&Parent>,
+    query_ring: Query<(&Ring, Option<&Children>)>,
+    query_electrons: Query<(), With<Electron>>,
+) {
+    let electron = trigger.entity();
+    let Ok((ring, children)) = query_parent
+        .get(electron)
+        .and_then(|parent| query_ring.get(parent.get()))
+    else {
+        return;
+    };
+    let children = children.map(|children| &children[..]).unwrap_or_default();
+    let mut electrons = children
+        .iter()
+        .filter(|child| query_electrons.contains(**child))
+        .count();
+    // The new electron may not have been added to its ring's children yet.
+    if !children.contains(&electron) {
+        electrons += 1;
+    }
+    if electrons >= ring.max_electrons && !progress.filled_rings.contains(&ring.index) {
+        progress.filled_rings.push(ring.index);
+    }
+}
+
+fn track_electron_clicks(
+    mut clicks: EventReader<Pointer<Click>>,
+    query_electrons: Query<(), With<Electron>>,
+    mut progress: ResMut<AchievementProgress>,
+) {
+    for click in clicks.read() {
+        if query_electrons.contains(click.target) {
+            progress.electron_clicks += 1;
+        }
+    }
+}
+
+fn unlock_achievements(
+    mut commands: Commands,
+    progress: Res<AchievementProgress>,
+    mut unlocked: ResMut<UnlockedAchievements>,
+    data_dir: Res<DataDir>,
+) {
+    let mut changed = false;
+    for achievement in ACHIEVEMENTS {
+        if unlocked.contains(achievement) || !achievement.goal.is_met(&progress) {
+            continue;
+        }
+        log::info!("Achievement unlocked: {}", achievement.name);
+        unlocked.0.push(achievement.id);
+        commands.trigger(AchievementUnlocked(achievement));
+        changed = true;
+    }
+    if changed {
+        unlocked.save(&data_dir);
+    }
+}
@@ -0,0 +1,199 @@
+//! Sprite sheet animation.
+//!
+//! A [`SpriteAnimation`] steps its sprite's [`TextureAtlas`] through the clip for its
+//! [`AnimationState`]. Particles get theirs from [`ImageAtlases`] as they spawn, and again with
+//! their image when another [`AssetVariant`](crate::game::assets::AssetVariant) is picked.
+//! Electrons are [`AnimationState::Excited`] while they cycle.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        assets::{HandleMap, ImageAtlases, ImageKey},
+        movement::RevolutionController,
+        spawn::atom::{Electron, Neutron, Proton},
+    },
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SpriteAnimation>();
+    app.observe(add_atlas_sprite::<Electron>(ImageKey::Electron))
+        .observe(add_atlas_sprite::<Proton>(ImageKey::Proton))
+        .observe(add_atlas_sprite::<Neutron>(ImageKey::Neutron));
+    app.add_systems(
+        Update,
+        (
+            tick_sprite_animations.in_set(AppSet::TickTimers),
+            (
+                restyle_particles.run_if(
+                    resource_changed::<HandleMap<ImageKey>>
+                        .or_else(resource_changed::<ImageAtlases>),
+                ),
+                excite_cycling_electrons,
+                apply_sprite_animations,
+            )
+                .chain()
+                .in_set(AppSet::Update),
+        ),
+    );
+}
+
+/// What a sprite is doing, which picks the clip it plays.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Reflect,
+)]
+pub enum AnimationState {
+    #[default]
+    Idle,
+    /// Played instead of [`AnimationState::Idle`] while an electron cycles.
+    Excited,
+}
+
+/// A run of frames in a texture atlas, played on a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Reflect)]
+pub struct AtlasClip {
+    /// The atlas index of the first frame.
+    pub first: usize,
+    pub frames: usize,
+    pub fps: f32,
+}
+
+impl AtlasClip {
+    fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f32(1. / self.fps)
+    }
+}
+
+/// Plays the clip for [`SpriteAnimation::state`] on the entity's [`TextureAtlas`].
+/// States without a clip of their own play the [`AnimationState::Idle`] clip.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct SpriteAnimation {
+    clips: HashMap<AnimationState, AtlasClip>,
+    state: AnimationState,
+    frame: usize,
+    timer: Timer,
+}
+
+impl SpriteAnimation {
+    pub fn new(clips: HashMap<AnimationState, AtlasClip>) -> Self {
+        let mut animation = Self {
+            clips,
+            state: AnimationState::Idle,
+            frame: 0,
+            timer: Timer::default(),
+        };
+        animation.restart();
+        animation
+    }
+
+    pub fn state(&self) -> AnimationState {
+        self.state
+    }
+
+    /// Switches to the clip for `state`, from its first frame.
+    pub fn set_state(&mut self, state: AnimationState) {
+        if self.state != state {
+            self.state = state;
+            self.restart();
+        }
+    }
+
+    /// The atlas index of the current frame.
+    pub fn index(&self) -> usize {
+        self.clip().map_or(0, |clip| clip.first + self.frame)
+    }
+
+    fn clip(&self) -> Option<&AtlasClip> {
+        self.clips
+            .get(&self.state)
+            .or_else(|| self.clips.get(&AnimationState::Idle))
+    }
+
+    fn restart(&mut self) {
+        self.frame = 0;
+        let duration = self.clip().map_or(Duration::MAX, AtlasClip::frame_duration);
+        self.timer = Timer::new(duration, TimerMode::Repeating);
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        let frames = self.clip().map_or(1, |clip| clip.frames.max(1));
+        let elapsed = self.timer.tick(delta).times_finished_this_tick() as usize;
+        self.frame = (self.frame + elapsed) % frames;
+    }
+}
+
+/// Gives every new `T` the atlas and animation for `key`, if its image is in an atlas.
+fn add_atlas_sprite<T: Component>(
+    key: ImageKey,
+) -> impl Fn(Trigger<OnAdd, T>, Commands, Res<ImageAtlases>) {
+    move |trigger, mut commands, atlases| {
+        if let Some(sprite) = atlases.sprite(key) {
+            commands.entity(trigger.entity()).insert(sprite);
+        }
+    }
+}
+
+/// Gives every particle its current image, atlas and animation, after the manifest or its
+/// variant has changed them.
+fn restyle_particles(
+    mut commands: Commands,
+    image_handles: Res<HandleMap<ImageKey>>,
+    atlases: Res<ImageAtlases>,
+    mut query_particle: Query<
+        (Entity, &mut Handle<Image>, Has<Electron>, Has<Proton>),
+        Or<(With<Electron>, With<Proton>, With<Neutron>)>,
+    >,
+) {
+    for (entity, mut texture, electron, proton) in &mut query_particle {
+        let key = if electron {
+            ImageKey::Electron
+        } else if proton {
+            ImageKey::Proton
+        } else {
+            ImageKey::Neutron
+        };
+        if let Some(handle) = image_handles.get(&key) {
+            if texture.id() != handle.id() {
+                *texture = handle.clone_weak();
+            }
+        }
+        match atlases.sprite(key) {
+            Some(sprite) => commands.entity(entity).insert(sprite),
+            None => commands
+                .entity(entity)
+                .remove::<(TextureAtlas, SpriteAnimation)>(),
+        };
+    }
+}
+
+fn tick_sprite_animations(time: Res<Time>, mut query_animation: Query<&mut SpriteAnimation>) {
+    for mut animation in &mut query_animation {
+        animation.tick(time.delta());
+    }
+}
+
+fn excite_cycling_electrons(
+    mut query_electron: Query<(&RevolutionController, &mut SpriteAnimation), With<Electron>>,
+) {
+    for (revolution, mut animation) in &mut query_electron {
+        let state = if revolution.count > 0 {
+            AnimationState::Excited
+        } else {
+            AnimationState::Idle
+        };
+        animation.set_state(state);
+    }
+}
+
+fn apply_sprite_animations(
+    mut query_animation: Query<(&SpriteAnimation, &mut TextureAtlas), Changed<SpriteAnimation>>,
+) {
+    for (animation, mut atlas) in &mut query_animation {
+        atlas.index = animation.index();
+    }
+}
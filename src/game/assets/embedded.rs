@@ -0,0 +1,19 @@
+//! Every asset, built into the executable so a native release is a single file.
+//!
+//! Enabled by the `embedded_assets` feature. The build script lists the files in `assets/`, and
+//! [`asset_path`](super::asset_path) points loads at the `embedded://` source holding them.
+
+use std::path::Path;
+
+use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+
+include!(concat!(env!("OUT_DIR"), "/embedded_assets.rs"));
+
+pub(super) fn plugin(app: &mut App) {
+    let registry = app.world().resource::<EmbeddedAssetRegistry>();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    for (path, bytes) in ASSETS {
+        // The full path lets `embedded_watcher` hot reload the file.
+        registry.insert_asset(root.join(path), Path::new(path), *bytes);
+    }
+}
@@ -0,0 +1,321 @@
+//! The manifest listing where every game asset is loaded from.
+//!
+//! The manifest is a RON file, [`MANIFEST_PATH`]. Each section lists the keys of one
+//! [`AssetKey`] by name. Variants list replacements for some entries, and the one in use is
+//! picked with [`AssetVariant`]. Picking one mid-run restyles the particles already spawned,
+//! and its assets load in the background rather than on a loading screen.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use super::{asset_path, AssetKey, HandleMap};
+use crate::game::animation::{AnimationState, AtlasClip};
+use crate::settings::Settings;
+
+pub const MANIFEST_PATH: &str = "game.manifest.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    let validators = ManifestValidators::default();
+    app.init_asset::<AssetManifest>();
+    app.register_asset_loader(AssetManifestLoader {
+        validators: validators.clone(),
+    });
+    app.insert_resource(validators);
+    // Only once the app is built, so every handle map has added its validator first.
+    app.add_systems(Startup, load_manifest);
+
+    app.init_resource::<AssetVariant>();
+    app.add_systems(
+        PreUpdate,
+        select_asset_variant.run_if(resource_changed::<Settings>),
+    );
+}
+
+/// Where a single asset is loaded from.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    /// How an image is sampled. Ignored for other assets.
+    #[serde(default)]
+    pub sampler: ManifestSampler,
+    /// Where an image's frames are in its sprite sheet, if it is one. Ignored for other assets.
+    #[serde(default)]
+    pub atlas: Option<ManifestAtlas>,
+}
+
+/// A sprite sheet laid out in a grid of square tiles, with clips played from it.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ManifestAtlas {
+    pub tile_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub clips: BTreeMap<AnimationState, AtlasClip>,
+}
+
+impl ManifestAtlas {
+    fn is_valid(&self) -> bool {
+        let tiles = (self.columns * self.rows) as usize;
+        self.clips
+            .values()
+            .all(|clip| clip.frames > 0 && clip.first + clip.frames <= tiles && clip.fps > 0.)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum ManifestSampler {
+    /// Whatever `ImagePlugin` defaults to.
+    #[default]
+    Default,
+    Nearest,
+    Linear,
+}
+
+/// Entries by section, then by key name.
+type Sections = BTreeMap<String, BTreeMap<String, ManifestEntry>>;
+
+/// Entries replacing some of the manifest's own.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct ManifestVariant {
+    /// Shown to the player when picking a variant.
+    name: String,
+    assets: Sections,
+}
+
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Deserialize)]
+pub struct AssetManifest {
+    assets: Sections,
+    /// Variants by id.
+    #[serde(default)]
+    variants: BTreeMap<String, ManifestVariant>,
+}
+
+impl AssetManifest {
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        ron::from_str(text).map_err(ManifestError::Parse)
+    }
+
+    /// The id and name of every variant.
+    pub fn variants(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.variants
+            .iter()
+            .map(|(id, variant)| (id.as_str(), variant.name.as_str()))
+    }
+
+    /// The entry for `key`, taken from `variant` if it replaces it.
+    pub fn entry<K: AssetKey>(&self, key: K, variant: Option<&str>) -> Option<&ManifestEntry> {
+        let name = key.variant_name();
+        variant
+            .and_then(|variant| self.variants.get(variant))
+            .and_then(|variant| variant.assets.get(K::SECTION)?.get(name))
+            .or_else(|| self.assets.get(K::SECTION)?.get(name))
+    }
+
+    /// Starts loading every asset for `K`, using `variant`'s entries where it has them.
+    pub fn handle_map<K: AssetKey>(
+        &self,
+        variant: Option<&str>,
+        asset_server: &AssetServer,
+    ) -> HandleMap<K> {
+        if let Some(variant) = variant.filter(|variant| !self.variants.contains_key(*variant)) {
+            log::warn!("Ignoring unknown asset variant {variant}");
+        }
+        K::ALL
+            .iter()
+            .filter_map(|key| {
+                let entry = self.entry(*key, variant)?;
+                Some((*key, K::load(entry, asset_server)))
+            })
+            .collect::<HashMap<_, _>>()
+            .into()
+    }
+
+    /// Checks that every `K` has an entry, and that no entry names a key `K` doesn't have.
+    pub fn validate<K: AssetKey>(&self) -> Result<(), ManifestError> {
+        let section = self.assets.get(K::SECTION);
+        for key in K::ALL {
+            if !section.is_some_and(|section| section.contains_key(key.variant_name())) {
+                return Err(ManifestError::MissingEntry {
+                    section: K::SECTION,
+                    key: key.variant_name().to_string(),
+                });
+            }
+        }
+
+        let variant_sections = self
+            .variants
+            .values()
+            .map(|variant| variant.assets.get(K::SECTION));
+        for (name, entry) in std::iter::once(section)
+            .chain(variant_sections)
+            .flatten()
+            .flatten()
+        {
+            if !K::ALL.iter().any(|key| key.variant_name() == name) {
+                return Err(ManifestError::UnknownKey {
+                    section: K::SECTION,
+                    key: name.clone(),
+                });
+            }
+            if entry.atlas.as_ref().is_some_and(|atlas| !atlas.is_valid()) {
+                return Err(ManifestError::InvalidAtlas {
+                    section: K::SECTION,
+                    key: name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    MissingEntry {
+        section: &'static str,
+        key: String,
+    },
+    UnknownKey {
+        section: &'static str,
+        key: String,
+    },
+    /// A clip is empty, has no frame rate, or runs past the end of its atlas.
+    InvalidAtlas {
+        section: &'static str,
+        key: String,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "cannot read the asset manifest: {error}"),
+            Self::Parse(error) => write!(f, "invalid asset manifest: {error}"),
+            Self::MissingEntry { section, key } => {
+                write!(f, "asset manifest has no {section} entry for {key}")
+            }
+            Self::UnknownKey { section, key } => {
+                write!(f, "asset manifest lists unknown {section} key {key}")
+            }
+            Self::InvalidAtlas { section, key } => {
+                write!(f, "asset manifest has an invalid atlas for {section} {key}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<std::io::Error> for ManifestError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// The loaded manifest.
+#[derive(Resource, Debug)]
+pub struct ManifestHandle(pub Handle<AssetManifest>);
+
+impl FromWorld for ManifestHandle {
+    fn from_world(world: &mut World) -> Self {
+        Self(
+            world
+                .resource::<AssetServer>()
+                .load(asset_path(MANIFEST_PATH)),
+        )
+    }
+}
+
+fn load_manifest(world: &mut World) {
+    world.init_resource::<ManifestHandle>();
+}
+
+type Validator = fn(&AssetManifest) -> Result<(), ManifestError>;
+
+/// Checks every manifest must pass to load, one for each [`HandleMap`].
+/// Shared with the loader, which runs off the main thread.
+#[derive(Resource, Clone, Default)]
+pub(super) struct ManifestValidators(Arc<RwLock<Vec<Validator>>>);
+
+impl ManifestValidators {
+    pub(super) fn add(&self, validator: Validator) {
+        if let Ok(mut validators) = self.0.write() {
+            validators.push(validator);
+        }
+    }
+
+    fn validate(&self, manifest: &AssetManifest) -> Result<(), ManifestError> {
+        let Ok(validators) = self.0.read() else {
+            return Ok(());
+        };
+        validators
+            .iter()
+            .try_for_each(|validator| validator(manifest))
+    }
+}
+
+struct AssetManifestLoader {
+    validators: ManifestValidators,
+}
+
+impl AssetLoader for AssetManifestLoader {
+    type Asset = AssetManifest;
+    type Settings = ();
+    type Error = ManifestError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<AssetManifest, ManifestError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+        let manifest = AssetManifest::parse(&text)?;
+        self.validators.validate(&manifest)?;
+        Ok(manifest)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["manifest.ron"]
+    }
+}
+
+/// Whether the manifest has loaded or changed, or another [`AssetVariant`] was picked, since
+/// this last returned `true`. Anything built from the manifest runs on this.
+pub(super) fn manifest_updated(
+    manifest: Res<ManifestHandle>,
+    manifests: Res<Assets<AssetManifest>>,
+    mut events: EventReader<AssetEvent<AssetManifest>>,
+    variant: Res<AssetVariant>,
+    mut built_variant: Local<Option<Option<String>>>,
+) -> bool {
+    let changed = events.read().any(|event| {
+        event.is_loaded_with_dependencies(&manifest.0) || event.is_modified(&manifest.0)
+    });
+    if !manifests.contains(&manifest.0) || (!changed && built_variant.as_ref() == Some(&variant.0))
+    {
+        return false;
+    }
+    *built_variant = Some(variant.0.clone());
+    true
+}
+
+/// The manifest variant replacing some of the manifest's assets, if any.
+/// Follows [`Settings::asset_variant`].
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetVariant(pub Option<String>);
+
+fn select_asset_variant(settings: Res<Settings>, mut variant: ResMut<AssetVariant>) {
+    variant.set_if_neq(AssetVariant(settings.asset_variant.clone()));
+}
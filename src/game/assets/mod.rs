@@ -0,0 +1,440 @@
+//! Game assets, loaded by key from the [`AssetManifest`].
+
+#[cfg(feature = "embedded_assets")]
+mod embedded;
+mod manifest;
+
+use std::{any::type_name, hash::Hash};
+
+use bevy::{
+    asset::{AssetLoadFailedEvent, AssetPath, LoadState, RecursiveDependencyLoadState},
+    prelude::*,
+    reflect::{Enum, GetTypeRegistration},
+    render::texture::{ImageLoaderSettings, ImageSampler},
+    utils::{HashMap, HashSet},
+};
+
+use manifest::{manifest_updated, ManifestValidators};
+pub use manifest::{
+    AssetManifest, AssetVariant, ManifestAtlas, ManifestEntry, ManifestError, ManifestHandle,
+    ManifestSampler, MANIFEST_PATH,
+};
+
+use crate::{
+    game::animation::{AnimationState, AtlasClip, SpriteAnimation},
+    screen::Screen,
+    AppConfig,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    // Embedded assets must be in place before anything loads.
+    #[cfg(feature = "embedded_assets")]
+    app.add_plugins(embedded::plugin);
+    app.add_plugins(manifest::plugin);
+    app.init_resource::<AssetProgress>();
+    app.add_systems(
+        PreUpdate,
+        track_manifest_progress.run_if(on_loading_screen()),
+    );
+    app.init_resource::<ImageAtlases>();
+    app.add_systems(PreUpdate, build_image_atlases.run_if(manifest_updated));
+
+    app.init_handle_map::<ImageKey>()
+        .init_handle_map::<SfxKey>()
+        .init_handle_map::<SoundtrackKey>();
+}
+
+/// An extension trait for adding [`HandleMap`]s to the [`App`].
+pub trait InitHandleMap {
+    /// Loads the assets for `K` listed in the [`AssetManifest`], and tracks their progress in
+    /// [`AssetProgress`].
+    fn init_handle_map<K>(&mut self) -> &mut Self
+    where
+        K: AssetKey + Send + Sync,
+        HandleMap<K>: GetTypeRegistration;
+}
+
+impl InitHandleMap for App {
+    fn init_handle_map<K>(&mut self) -> &mut Self
+    where
+        K: AssetKey + Send + Sync,
+        HandleMap<K>: GetTypeRegistration,
+    {
+        self.register_type::<HandleMap<K>>();
+        self.init_resource::<HandleMap<K>>();
+        self.world()
+            .resource::<ManifestValidators>()
+            .add(AssetManifest::validate::<K>);
+        self.add_systems(
+            PreUpdate,
+            (
+                build_handle_map::<K>.run_if(manifest_updated),
+                track_load_progress::<K>.run_if(on_loading_screen()),
+            )
+                .chain(),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
+pub enum ImageKey {
+    Electron,
+    Proton,
+    Neutron,
+}
+
+impl AssetKey for ImageKey {
+    type Asset = Image;
+    const ALL: &'static [Self] = &[Self::Electron, Self::Proton, Self::Neutron];
+    const SECTION: &'static str = "images";
+
+    fn stand_in(config: &AppConfig) -> Option<fn() -> Image> {
+        // Images can't be loaded without a renderer.
+        (!config.render).then_some(Image::default)
+    }
+
+    fn load(entry: &ManifestEntry, asset_server: &AssetServer) -> Handle<Image> {
+        let sampler = match entry.sampler {
+            ManifestSampler::Default => return asset_server.load(asset_path(&entry.path)),
+            ManifestSampler::Nearest => ImageSampler::nearest(),
+            ManifestSampler::Linear => ImageSampler::linear(),
+        };
+        asset_server.load_with_settings(
+            asset_path(&entry.path),
+            move |settings: &mut ImageLoaderSettings| {
+                settings.sampler = sampler.clone();
+            },
+        )
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
+pub enum SfxKey {
+    ButtonHover,
+    ButtonPress,
+    Step1,
+    Step2,
+    Step3,
+    Step4,
+}
+
+impl AssetKey for SfxKey {
+    type Asset = AudioSource;
+    const ALL: &'static [Self] = &[
+        Self::ButtonHover,
+        Self::ButtonPress,
+        Self::Step1,
+        Self::Step2,
+        Self::Step3,
+        Self::Step4,
+    ];
+    const SECTION: &'static str = "sfx";
+
+    fn stand_in(config: &AppConfig) -> Option<fn() -> AudioSource> {
+        (!config.audio).then_some(silence)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
+pub enum SoundtrackKey {
+    Credits,
+    Gameplay,
+}
+
+impl AssetKey for SoundtrackKey {
+    type Asset = AudioSource;
+    const ALL: &'static [Self] = &[Self::Credits, Self::Gameplay];
+    const SECTION: &'static str = "soundtracks";
+
+    fn stand_in(config: &AppConfig) -> Option<fn() -> AudioSource> {
+        (!config.audio).then_some(silence)
+    }
+}
+
+/// Names a game asset, which is listed in the [`AssetManifest`] under the key's variant name.
+pub trait AssetKey: Enum + Copy + Eq + Hash + Sized + 'static {
+    type Asset: Asset;
+    const ALL: &'static [Self];
+    /// The manifest section listing these keys.
+    const SECTION: &'static str;
+
+    /// Makes a stand-in for every asset, if the real ones can't be used under `config`.
+    fn stand_in(_config: &AppConfig) -> Option<fn() -> Self::Asset> {
+        None
+    }
+
+    /// Starts loading the asset `entry` describes.
+    fn load(entry: &ManifestEntry, asset_server: &AssetServer) -> Handle<Self::Asset> {
+        asset_server.load(asset_path(&entry.path))
+    }
+}
+
+/// Where to load the file at `path` in `assets/` from. That's the executable itself with the
+/// `embedded_assets` feature, and the `assets/` folder otherwise.
+pub fn asset_path(path: &str) -> AssetPath<'static> {
+    let path = AssetPath::from(path.to_string());
+    #[cfg(feature = "embedded_assets")]
+    let path = path.with_source("embedded");
+    path
+}
+
+/// A sound with no data, standing in for real sounds when audio is off.
+fn silence() -> AudioSource {
+    AudioSource {
+        bytes: Vec::new().into(),
+    }
+}
+
+#[derive(Resource, Reflect, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct HandleMap<K: AssetKey>(HashMap<K, Handle<K::Asset>>);
+
+impl<K: AssetKey, T> From<T> for HandleMap<K>
+where
+    T: Into<HashMap<K, Handle<K::Asset>>>,
+{
+    fn from(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<K: AssetKey> HandleMap<K> {
+    /// How far along loading every handle is.
+    /// Keys without a handle yet, before the manifest has loaded, count as not loaded.
+    pub fn load_progress(&self, asset_server: &AssetServer) -> LoadProgress {
+        LoadProgress {
+            total: K::ALL.len(),
+            ..LoadProgress::of(asset_server, self.values())
+        }
+    }
+}
+
+impl<K: AssetKey> FromWorld for HandleMap<K> {
+    /// Stand-ins if [`AssetKey::stand_in`] has them, otherwise empty until the manifest has
+    /// loaded.
+    fn from_world(world: &mut World) -> Self {
+        let Some(stand_in) = K::stand_in(world.resource::<AppConfig>()) else {
+            return Self(HashMap::new());
+        };
+        let mut assets = world.resource_mut::<Assets<K::Asset>>();
+        K::ALL
+            .iter()
+            .map(|key| (*key, assets.add(stand_in())))
+            .collect::<HashMap<_, _>>()
+            .into()
+    }
+}
+
+/// How far along loading a set of assets is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    pub loaded: usize,
+    pub total: usize,
+    /// Paths of the assets that failed to load.
+    pub failed: Vec<String>,
+    /// Paths of the assets still loading.
+    pub pending: Vec<String>,
+}
+
+impl LoadProgress {
+    /// Handles added straight to `Assets` instead of loaded, like stand-ins, are always loaded.
+    fn of<'a, A: Asset>(
+        asset_server: &AssetServer,
+        handles: impl IntoIterator<Item = &'a Handle<A>>,
+    ) -> Self {
+        let mut progress = Self::default();
+        for handle in handles {
+            progress.total += 1;
+            let Some((state, _, recursive_state)) = asset_server.get_load_states(handle) else {
+                progress.loaded += 1;
+                continue;
+            };
+            let path = asset_server
+                .get_path(handle)
+                .map_or_else(|| format!("{:?}", handle.id()), |path| path.to_string());
+            match (state, recursive_state) {
+                (LoadState::Failed(_), _) | (_, RecursiveDependencyLoadState::Failed) => {
+                    progress.failed.push(path)
+                }
+                (LoadState::Loaded, RecursiveDependencyLoadState::Loaded) => progress.loaded += 1,
+                _ => progress.pending.push(path),
+            }
+        }
+        progress
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.loaded == self.total
+    }
+
+    /// The share of assets loaded, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+}
+
+/// The [`LoadProgress`] of the [`AssetManifest`], and every [`HandleMap`] added with
+/// [`InitHandleMap::init_handle_map`]. Only tracked on the loading screens, which show it.
+#[derive(Resource, Debug, Default)]
+pub struct AssetProgress {
+    maps: HashMap<&'static str, LoadProgress>,
+    /// Paths being loaded again, which count as loading until they load or fail again.
+    retrying: HashSet<String>,
+}
+
+impl AssetProgress {
+    /// Whether every asset has loaded. Not until progress has been tracked at all.
+    pub fn is_done(&self) -> bool {
+        !self.maps.is_empty() && self.total().is_done()
+    }
+
+    /// The progress of everything together.
+    pub fn total(&self) -> LoadProgress {
+        let mut total = LoadProgress::default();
+        for progress in self.maps.values() {
+            total.loaded += progress.loaded;
+            total.total += progress.total;
+            total.pending.extend(progress.pending.iter().cloned());
+            for path in &progress.failed {
+                if self.retrying.contains(path) {
+                    total.pending.push(path.clone());
+                } else {
+                    total.failed.push(path.clone());
+                }
+            }
+        }
+        total.failed.sort();
+        total.pending.sort();
+        total
+    }
+
+    /// Loads every asset that failed or is still loading again.
+    pub fn retry(&mut self, asset_server: &AssetServer) {
+        let total = self.total();
+        for path in total.failed.into_iter().chain(total.pending) {
+            log::info!("Reloading {path}");
+            asset_server.reload(path.clone());
+            self.retrying.insert(path);
+        }
+    }
+
+    fn update<A: Asset>(
+        &mut self,
+        name: &'static str,
+        current: LoadProgress,
+        failures: &mut EventReader<AssetLoadFailedEvent<A>>,
+    ) {
+        for failure in failures.read() {
+            self.retrying.remove(&failure.path.to_string());
+        }
+        if self.maps.get(name) != Some(&current) {
+            self.maps.insert(name, current);
+        }
+    }
+}
+
+fn on_loading_screen() -> impl Condition<()> {
+    in_state(Screen::Loading).or_else(in_state(Screen::LoadingFailed))
+}
+
+fn track_load_progress<K: AssetKey + Send + Sync>(
+    asset_server: Res<AssetServer>,
+    handles: Res<HandleMap<K>>,
+    mut failures: EventReader<AssetLoadFailedEvent<K::Asset>>,
+    mut progress: ResMut<AssetProgress>,
+) {
+    let current = handles.load_progress(&asset_server);
+    progress.update(type_name::<K>(), current, &mut failures);
+}
+
+fn track_manifest_progress(
+    asset_server: Res<AssetServer>,
+    manifest: Res<ManifestHandle>,
+    mut failures: EventReader<AssetLoadFailedEvent<AssetManifest>>,
+    mut progress: ResMut<AssetProgress>,
+) {
+    let current = LoadProgress::of(&asset_server, [&manifest.0]);
+    progress.update(type_name::<AssetManifest>(), current, &mut failures);
+}
+
+/// Fills `HandleMap<K>` from the manifest, unless it holds stand-ins.
+fn build_handle_map<K: AssetKey + Send + Sync>(
+    config: Res<AppConfig>,
+    asset_server: Res<AssetServer>,
+    manifest: Res<ManifestHandle>,
+    manifests: Res<Assets<AssetManifest>>,
+    variant: Res<AssetVariant>,
+    mut handles: ResMut<HandleMap<K>>,
+) {
+    if K::stand_in(&config).is_some() {
+        return;
+    }
+    let Some(manifest) = manifests.get(&manifest.0) else {
+        return;
+    };
+    *handles = manifest.handle_map(variant.0.as_deref(), &asset_server);
+}
+
+/// The sprite sheet layout and clips of every [`ImageKey`] whose image is a sprite sheet.
+#[derive(Resource, Debug, Default)]
+pub struct ImageAtlases(HashMap<ImageKey, ImageAtlas>);
+
+#[derive(Debug, Clone)]
+pub struct ImageAtlas {
+    pub layout: Handle<TextureAtlasLayout>,
+    pub clips: HashMap<AnimationState, AtlasClip>,
+}
+
+impl ImageAtlases {
+    pub fn get(&self, key: ImageKey) -> Option<&ImageAtlas> {
+        self.0.get(&key)
+    }
+
+    /// The atlas and animation for a sprite showing `key`, if its image is a sprite sheet.
+    pub fn sprite(&self, key: ImageKey) -> Option<(TextureAtlas, SpriteAnimation)> {
+        let atlas = self.get(key)?;
+        let animation = SpriteAnimation::new(atlas.clips.clone());
+        let texture_atlas = TextureAtlas {
+            layout: atlas.layout.clone(),
+            index: animation.index(),
+        };
+        Some((texture_atlas, animation))
+    }
+}
+
+fn build_image_atlases(
+    manifest: Res<ManifestHandle>,
+    manifests: Res<Assets<AssetManifest>>,
+    variant: Res<AssetVariant>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlases: ResMut<ImageAtlases>,
+) {
+    let Some(manifest) = manifests.get(&manifest.0) else {
+        return;
+    };
+    atlases.0 = ImageKey::ALL
+        .iter()
+        .filter_map(|key| {
+            let atlas = manifest.entry(*key, variant.0.as_deref())?.atlas.as_ref()?;
+            let layout = TextureAtlasLayout::from_grid(
+                UVec2::splat(atlas.tile_size),
+                atlas.columns,
+                atlas.rows,
+                None,
+                None,
+            );
+            let clips = atlas.clips.iter().map(|(state, clip)| (*state, *clip));
+            Some((
+                *key,
+                ImageAtlas {
+                    layout: layouts.add(layout),
+                    clips: clips.collect(),
+                },
+            ))
+        })
+        .collect();
+}
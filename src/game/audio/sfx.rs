@@ -1,30 +1,48 @@
 use bevy::{audio::PlaybackMode, prelude::*};
 
 use crate::game::assets::{HandleMap, SfxKey};
+use crate::game::upgrades::PurchaseCompleted;
+use crate::AppConfig;
 
 pub(super) fn plugin(app: &mut App) {
-    app.observe(play_sfx);
+    app.observe(play_sfx).observe(play_purchase_sfx);
 }
 
 fn play_sfx(
     trigger: Trigger<PlaySfx>,
     mut commands: Commands,
     sfx_handles: Res<HandleMap<SfxKey>>,
+    config: Res<AppConfig>,
 ) {
-    let sfx_key = match trigger.event() {
-        PlaySfx::Key(key) => *key,
+    // Nothing would despawn the sound once it finished.
+    if !config.audio {
+        return;
+    }
+    let (sfx_key, speed) = match trigger.event() {
+        PlaySfx::Key(key) => (*key, 1.0),
+        PlaySfx::Denied => (SfxKey::ButtonPress, DENIED_SPEED),
     };
     commands.spawn(AudioSourceBundle {
         source: sfx_handles[&sfx_key].clone_weak(),
         settings: PlaybackSettings {
             mode: PlaybackMode::Despawn,
+            speed,
             ..default()
         },
     });
 }
 
+fn play_purchase_sfx(_trigger: Trigger<PurchaseCompleted>, mut commands: Commands) {
+    commands.trigger(PlaySfx::Key(SfxKey::Step1));
+}
+
+/// Playback speed of [`PlaySfx::Denied`], slowed down so it sounds lower than a regular press.
+const DENIED_SPEED: f32 = 0.5;
+
 /// Trigger this event to play a single sound effect.
 #[derive(Event)]
 pub enum PlaySfx {
     Key(SfxKey),
+    /// A low button press, for actions that were refused.
+    Denied,
 }
@@ -0,0 +1,245 @@
+//! Keep the atom in view as it grows, and let the player zoom and pan around it.
+//!
+//! The camera frames the atom in the space between the upgrades and stats panels, zooming out
+//! smoothly as rings are added or the window shrinks. On top of that the player zooms with the
+//! mouse wheel or a pinch, and pans by dragging with the right or middle mouse button, or two
+//! fingers.
+
+use bevy::{
+    input::{
+        mouse::{MouseScrollUnit, MouseWheel},
+        touch::Touch,
+    },
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_mod_picking::{focus::HoverMap, pointer::PointerId};
+
+use crate::{
+    game::{
+        spawn::atom::Ring,
+        ui::{
+            stats_ui::{STATS_PANEL_MARGIN, STATS_PANEL_WIDTH},
+            upgrades::UPGRADES_PANEL_WIDTH,
+        },
+    },
+    screen::InRun,
+    AppSet,
+};
+
+/// The UI down the left of the screen, in pixels.
+const LEFT_UI_WIDTH: f32 = UPGRADES_PANEL_WIDTH;
+/// The UI down the right of the screen, in pixels.
+const RIGHT_UI_WIDTH: f32 = STATS_PANEL_WIDTH + STATS_PANEL_MARGIN;
+/// Space left around the atom when framing it, in pixels.
+const FRAME_MARGIN: f32 = 24.;
+/// How far past its outermost ring the atom reaches, for the electrons on it.
+const ELECTRON_RADIUS: f32 = 16.;
+/// Below this much room between the panels, the atom is framed in the whole window and the
+/// panels cover part of it.
+const MIN_FRAME_WIDTH: f32 = 240.;
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 4.;
+/// The zoom change for each line the mouse wheel scrolls.
+const ZOOM_STEP: f32 = 1.1;
+/// How quickly the camera catches up with where it should be, per second.
+const FRAMING_SPEED: f32 = 8.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraView>();
+    app.init_resource::<CameraView>();
+    app.add_systems(OnExit(InRun), reset_camera);
+    app.add_systems(
+        Update,
+        (
+            (zoom_camera, pan_camera).in_set(AppSet::RecordInput),
+            frame_atom.in_set(AppSet::Update),
+        )
+            .run_if(in_state(InRun)),
+    );
+}
+
+/// How the player has moved the camera from where it frames the atom.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct CameraView {
+    /// Above 1 to magnify the atom, below 1 to see more around it.
+    pub zoom: f32,
+    /// How far the camera has been moved from the atom, in world units.
+    pub pan: Vec2,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            pan: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraView {
+    fn zoom_by(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+/// Where the camera looks to fit the atom between the UI, before the player's [`CameraView`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Framing {
+    /// World units per pixel. Never below 1, so small atoms aren't blown up.
+    pub scale: f32,
+    /// The point in the world at the center of the viewport. The atom is at the origin.
+    pub center: Vec2,
+}
+
+impl Framing {
+    /// Fits an atom of `atom_radius` into a viewport of `viewport` logical pixels.
+    pub fn fit(viewport: Vec2, atom_radius: f32) -> Self {
+        let between_panels = viewport.x - LEFT_UI_WIDTH - RIGHT_UI_WIDTH;
+        let (area, offset) = if between_panels >= MIN_FRAME_WIDTH {
+            (
+                Vec2::new(between_panels, viewport.y),
+                (LEFT_UI_WIDTH - RIGHT_UI_WIDTH) / 2.,
+            )
+        } else {
+            (viewport, 0.)
+        };
+        let room = (area - 2. * FRAME_MARGIN).max(Vec2::ONE);
+        let scale = (2. * atom_radius / room.min_element()).max(1.);
+        Self {
+            scale,
+            // Look to the left of the atom, which puts it in the middle of the area.
+            center: Vec2::new(-offset * scale, 0.),
+        }
+    }
+}
+
+fn reset_camera(
+    mut view: ResMut<CameraView>,
+    mut query_camera: Query<(&mut Transform, &mut OrthographicProjection), With<IsDefaultUiCamera>>,
+) {
+    *view = CameraView::default();
+    for (mut transform, mut projection) in &mut query_camera {
+        transform.translation = Vec2::ZERO.extend(transform.translation.z);
+        projection.scale = 1.;
+    }
+}
+
+/// Whether the mouse is over the UI rather than the atom.
+fn mouse_over_ui(hover_map: &HoverMap, query_node: &Query<(), With<Node>>) -> bool {
+    hover_map
+        .get(&PointerId::Mouse)
+        .is_some_and(|hovered| hovered.keys().any(|entity| query_node.contains(*entity)))
+}
+
+fn zoom_camera(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    query_node: Query<(), With<Node>>,
+    mut view: ResMut<CameraView>,
+) {
+    // The mouse wheel scrolls the UI it's over instead.
+    if !mouse_over_ui(&hover_map, &query_node) {
+        for event in mouse_wheel_events.read() {
+            let lines = match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / 20.,
+            };
+            view.zoom_by(ZOOM_STEP.powf(lines));
+        }
+    }
+    mouse_wheel_events.clear();
+
+    if let Some([a, b]) = two_touches(&touches) {
+        let before = a.previous_position().distance(b.previous_position());
+        let after = a.position().distance(b.position());
+        if before > 0. {
+            view.zoom_by(after / before);
+        }
+    }
+}
+
+fn pan_camera(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    query_node: Query<(), With<Node>>,
+    query_window: Query<&Window, With<PrimaryWindow>>,
+    query_projection: Query<&OrthographicProjection, With<IsDefaultUiCamera>>,
+    mut view: ResMut<CameraView>,
+    mut dragging: Local<bool>,
+    mut last_cursor: Local<Option<Vec2>>,
+) {
+    let drag_buttons = [MouseButton::Right, MouseButton::Middle];
+    if mouse_buttons.any_just_pressed(drag_buttons) {
+        // Drags that start over the UI are left to the UI.
+        *dragging = !mouse_over_ui(&hover_map, &query_node);
+    }
+    if !mouse_buttons.any_pressed(drag_buttons) {
+        *dragging = false;
+    }
+    let cursor = query_window
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position);
+
+    let mut moved = Vec2::ZERO;
+    if let (true, Some(before), Some(after)) = (*dragging, *last_cursor, cursor) {
+        moved += after - before;
+    }
+    *last_cursor = cursor;
+    if let Some([a, b]) = two_touches(&touches) {
+        moved += (a.delta() + b.delta()) / 2.;
+    }
+    if moved == Vec2::ZERO {
+        return;
+    }
+    let scale = query_projection
+        .get_single()
+        .map_or(1., |projection| projection.scale);
+    // The atom follows the pointer, so the camera moves the other way. Screen y points down.
+    view.pan += Vec2::new(-moved.x, moved.y) * scale;
+}
+
+fn two_touches(touches: &Touches) -> Option<[&Touch; 2]> {
+    let mut pressed = touches.iter();
+    match (pressed.next(), pressed.next(), pressed.next()) {
+        (Some(a), Some(b), None) => Some([a, b]),
+        _ => None,
+    }
+}
+
+/// Moves the camera smoothly towards the framing of the atom, adjusted by the [`CameraView`].
+fn frame_atom(
+    time: Res<Time>,
+    mut view: ResMut<CameraView>,
+    query_ring: Query<&Ring>,
+    mut query_camera: Query<
+        (&Camera, &mut Transform, &mut OrthographicProjection),
+        With<IsDefaultUiCamera>,
+    >,
+) {
+    let Ok((camera, mut transform, mut projection)) = query_camera.get_single_mut() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    let atom_radius = query_ring.iter().map(Ring::radius).fold(0., f32::max) + ELECTRON_RADIUS;
+    // Don't let the atom be panned out of view entirely.
+    let pan = view.pan.clamp_length_max(atom_radius);
+    if pan != view.pan {
+        view.pan = pan;
+    }
+
+    let framing = Framing::fit(viewport, atom_radius);
+    let scale = framing.scale / view.zoom;
+    let center = framing.center / view.zoom + view.pan;
+
+    let t = 1. - (-FRAMING_SPEED * time.delta_seconds()).exp();
+    projection.scale = projection.scale.lerp(scale, t);
+    let translation = transform.translation.truncate().lerp(center, t);
+    transform.translation = translation.extend(transform.translation.z);
+}
@@ -1,38 +1,42 @@
 //! Count the number of cycles for use as currency
 
 use crate::game::upgrades::costs::STARTING_CYCLES;
-use crate::AppSet;
+use crate::screen::InRun;
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CycleCount>();
     app.insert_resource(CycleCount(STARTING_CYCLES))
-        .observe(add_cycle)
-        .add_systems(
-            Update,
-            record_movement_controller.in_set(AppSet::RecordInput),
-        );
+        .add_systems(OnEnter(InRun), reset_cycle_count)
+        .observe(add_cycle);
 }
 
+/// Trigger this event when an electron completes a revolution.
 #[derive(Event, Debug)]
-pub struct AddCycle;
+pub struct AddCycle {
+    /// Index of the ring the electron is orbiting.
+    pub ring: usize,
+    /// What started the revolution.
+    pub source: CycleSource,
+}
+
+/// Whether a revolution was started by the player or by a ring's cycle timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum CycleSource {
+    #[default]
+    Manual,
+    Automatic,
+}
 
 #[derive(Resource, Debug, Clone, PartialEq, Reflect)]
 #[reflect(Resource)]
 pub struct CycleCount(pub u32);
 
+fn reset_cycle_count(mut count: ResMut<CycleCount>) {
+    count.0 = STARTING_CYCLES;
+}
+
 fn add_cycle(_trigger: Trigger<AddCycle>, mut count: ResMut<CycleCount>) {
     count.0 += 1;
     log::info!("Added to cycle count: {}", count.0)
 }
-
-fn record_movement_controller(
-    mut input: ResMut<ButtonInput<KeyCode>>,
-    mut cycle_count: ResMut<CycleCount>,
-) {
-    if input.clear_just_pressed(KeyCode::KeyM) {
-        cycle_count.0 += 1;
-    }
-    if input.clear_just_pressed(KeyCode::KeyN) {
-        cycle_count.0 += 10;
-    }
-}
@@ -2,22 +2,42 @@
 
 use bevy::prelude::*;
 
+pub mod achievements;
+pub mod animation;
 pub mod assets;
 pub mod audio;
+pub mod camera;
 pub mod cycles;
-mod movement;
+pub mod elements;
+pub mod movement;
+pub mod replay;
+pub mod ring_material;
+pub mod rng;
 pub mod spawn;
+pub mod stats;
+pub mod trails;
+pub mod tutorial;
 mod ui;
 pub mod upgrades;
+pub mod victory;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         audio::plugin,
+        camera::plugin,
         assets::plugin,
         movement::plugin,
+        replay::plugin,
+        rng::plugin,
         spawn::plugin,
+        stats::plugin,
+        achievements::plugin,
+        tutorial::plugin,
         cycles::plugin,
         ui::plugin,
         upgrades::plugin,
+        victory::plugin,
     ));
+    // Visuals that only follow the game's state.
+    app.add_plugins((animation::plugin, ring_material::plugin, trails::plugin));
 }
@@ -4,14 +4,22 @@
 //! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/main/examples/movement/physics_in_fixed_timestep.rs).
 
 use bevy::prelude::*;
-use std::f32::consts::PI;
+use std::{collections::VecDeque, f32::consts::PI};
 
 use crate::game::spawn::atom::InNucleus;
-use crate::{game::cycles::AddCycle, AppSet};
+use crate::game::spawn::atom::Ring;
+use crate::{
+    game::cycles::{AddCycle, CycleSource},
+    input::{ActionInput, InputAction},
+    AppSet,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Record directional input as movement controls.
     app.register_type::<MovementController>();
+    app.register_type::<BaseTransform>();
+    app.register_type::<Revolve>();
+    app.register_type::<RevolutionController>();
     app.add_systems(
         Update,
         record_movement_controller.in_set(AppSet::RecordInput),
@@ -42,11 +50,11 @@ impl MovementController {
 }
 
 fn record_movement_controller(
-    mut input: ResMut<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut controller_query: Query<&mut MovementController>,
 ) {
     // Collect directional input.
-    let add_count = input.clear_just_pressed(KeyCode::Space);
+    let add_count = input.just_pressed(InputAction::ManualCycle);
 
     // Apply movement intent to controllers.
     for mut controller in &mut controller_query {
@@ -59,7 +67,7 @@ fn record_movement_controller(
 fn apply_movement(mut movement_query: Query<(&mut MovementController, &mut RevolutionController)>) {
     for (mut controller, mut count) in &mut movement_query {
         if controller.add_count && count.refire_allowed() {
-            count.add_count();
+            count.add_count(CycleSource::Manual);
             controller.add_count = false
         }
     }
@@ -88,6 +96,8 @@ pub struct RevolutionController {
     pub count: u32,
     pub count_max: u32,
     pub refire_angle: f32,
+    /// What started each queued revolution, the current one first.
+    sources: VecDeque<CycleSource>,
     angle: f32,
 }
 
@@ -98,11 +108,20 @@ impl RevolutionController {
             count_max,
             angle: 0.,
             refire_angle,
+            sources: VecDeque::new(),
         }
     }
 
-    pub fn add_count(&mut self) {
-        self.count = std::cmp::min(self.count + 1, self.count_max);
+    pub fn add_count(&mut self, source: CycleSource) {
+        if self.count < self.count_max {
+            self.count += 1;
+            self.sources.push_back(source);
+        }
+    }
+
+    /// How far the entity has revolved in the current cycle, in radians.
+    pub fn angle(&self) -> f32 {
+        self.angle
     }
 
     pub fn refire_allowed(&self) -> bool {
@@ -117,11 +136,11 @@ fn apply_revolve(
         &mut Transform,
         &BaseTransform,
     )>,
-    query_parent: Query<&Revolve>,
+    query_parent: Query<(&Ring, &Revolve)>,
     mut commands: Commands,
 ) {
     for (parent, mut count, mut transform, base) in &mut movement_query {
-        let Ok(revolve) = query_parent.get(parent.get()) else {
+        let Ok((ring, revolve)) = query_parent.get(parent.get()) else {
             continue;
         };
         if count.count > 0 {
@@ -129,7 +148,10 @@ fn apply_revolve(
             if count.angle > 2.0 * PI {
                 count.angle = 0.0;
                 count.count -= 1;
-                commands.trigger(AddCycle)
+                commands.trigger(AddCycle {
+                    ring: ring.index,
+                    source: count.sources.pop_front().unwrap_or_default(),
+                })
             }
             transform.rotation = base.0.rotation;
             transform.translation = base.0.translation;
@@ -0,0 +1,461 @@
+//! Records every run as a stream of player actions, and plays recordings back.
+//!
+//! A [`Replay`] holds the run's [`GameRng`] seed and each action with the time since the run
+//! started. The last run is saved when it ends, so it can be attached to bug reports.
+//! Insert a [`ReplayPlayback`] before a run starts to play a replay instead of recording.
+//!
+//! Actions are stamped with game time, which stops while the game is paused and follows its
+//! time scale, so playback lines up however long frames take. Pausing and changing the time
+//! scale are recorded as actions of their own. Actions taken while paused all share the time
+//! the game was paused at, and are played back in the order they were taken.
+
+use std::{fmt, path::Path, time::Duration};
+
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::{
+    game::{
+        movement::MovementController,
+        rng::GameRng,
+        spawn::atom::{Electron, Ring},
+        upgrades::{PurchaseMultiplier, PurchaseRequested, Upgrades},
+    },
+    input::{ActionInput, InputAction},
+    persistence::DataDir,
+    screen::{InRun, Screen},
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ReplayClock>();
+    app.init_resource::<ReplayRecorder>();
+    app.add_systems(OnEnter(InRun), (start_recording, start_playback));
+    app.add_systems(OnExit(InRun), save_recording.run_if(not(is_playing_back)));
+    app.add_systems(OnEnter(Screen::Playing), forget_time_settings);
+    app.add_systems(PreUpdate, tick_replay_clock.run_if(in_state(InRun)));
+    app.add_systems(
+        Update,
+        (
+            (
+                record_input,
+                record_time_settings.run_if(in_state(Screen::Playing)),
+            )
+                .run_if(not(is_playing_back)),
+            play_back.run_if(is_playing_back),
+        )
+            .in_set(AppSet::RecordInput)
+            .run_if(in_state(InRun)),
+    );
+    app.observe(record_purchase);
+}
+
+/// Something the player did during a run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayAction {
+    ManualCycle,
+    /// Clicked the `electron`th electron on the ring at `ring`, both counting from zero.
+    ClickElectron {
+        ring: usize,
+        electron: usize,
+    },
+    Purchase {
+        upgrade: String,
+        /// The index of the ring the upgrade was bought for, if it was bought for a ring.
+        ring: Option<usize>,
+        multiplier: PurchaseMultiplier,
+    },
+    SetPaused(bool),
+    SetTimeScale(f32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayEvent {
+    /// Game time since the run started, see [`ReplayClock`].
+    pub time: Duration,
+    pub action: ReplayAction,
+}
+
+/// A recorded run.
+///
+/// Written as text, one line for the seed and then one line per action:
+///
+/// ```text
+/// seed 1234
+/// 0.500000000 buy buy_next_ring - x1
+/// 1.250000000 cycle
+/// 2.000000000 click 0 1
+/// 3.000000000 speed 2
+/// 4.000000000 pause
+/// 5.000000000 resume
+/// ```
+///
+/// Times are written to the nanosecond, so each action is played back on the same frame.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Replay {
+    pub seed: u64,
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    const SAVE_KEY: &'static str = "last_replay";
+
+    /// The last run, saved when it ended.
+    pub fn last(data_dir: &DataDir) -> Option<Self> {
+        let text = data_dir.load(Self::SAVE_KEY)?;
+        Self::parse(&text)
+            .inspect_err(|error| log::warn!("Cannot read the last replay: {error}"))
+            .ok()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_string())
+            .map_err(|error| format!("Cannot write {}: {error}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let seed = lines
+            .next()
+            .and_then(|(_, line)| line.trim().strip_prefix("seed "))
+            .and_then(|seed| seed.trim().parse().ok())
+            .ok_or("A replay must start with its seed")?;
+        let events = lines
+            .map(|(index, line)| {
+                parse_event(line).ok_or(format!("Invalid replay line {}: {line:?}", index + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { seed, events })
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        for ReplayEvent { time, action } in &self.events {
+            write!(f, "{}.{:09} ", time.as_secs(), time.subsec_nanos())?;
+            match action {
+                ReplayAction::ManualCycle => writeln!(f, "cycle")?,
+                ReplayAction::ClickElectron { ring, electron } => {
+                    writeln!(f, "click {ring} {electron}")?
+                }
+                ReplayAction::Purchase {
+                    upgrade,
+                    ring,
+                    multiplier,
+                } => {
+                    let ring = ring.map_or("-".to_string(), |ring| ring.to_string());
+                    writeln!(f, "buy {upgrade} {ring} {}", multiplier.label())?
+                }
+                ReplayAction::SetPaused(true) => writeln!(f, "pause")?,
+                ReplayAction::SetPaused(false) => writeln!(f, "resume")?,
+                ReplayAction::SetTimeScale(scale) => writeln!(f, "speed {scale}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_event(line: &str) -> Option<ReplayEvent> {
+    let mut words = line.split_whitespace();
+    let (secs, nanos) = words.next()?.split_once('.')?;
+    if nanos.len() != 9 {
+        return None;
+    }
+    let time = Duration::new(secs.parse().ok()?, nanos.parse().ok()?);
+    let action = match words.next()? {
+        "cycle" => ReplayAction::ManualCycle,
+        "click" => ReplayAction::ClickElectron {
+            ring: words.next()?.parse().ok()?,
+            electron: words.next()?.parse().ok()?,
+        },
+        "buy" => ReplayAction::Purchase {
+            upgrade: words.next()?.to_string(),
+            ring: match words.next()? {
+                "-" => None,
+                ring => Some(ring.parse().ok()?),
+            },
+            multiplier: {
+                let label = words.next()?;
+                PurchaseMultiplier::ALL
+                    .into_iter()
+                    .find(|multiplier| multiplier.label() == label)?
+            },
+        },
+        "pause" => ReplayAction::SetPaused(true),
+        "resume" => ReplayAction::SetPaused(false),
+        "speed" => ReplayAction::SetTimeScale(words.next()?.parse().ok()?),
+        _ => return None,
+    };
+    words
+        .next()
+        .is_none()
+        .then_some(ReplayEvent { time, action })
+}
+
+/// Game time since the run started. It stops while the game is paused and follows the time
+/// scale, so a replay lines up with the game however fast its frames run.
+#[derive(Resource, Debug, Default)]
+pub struct ReplayClock(Duration);
+
+impl ReplayClock {
+    pub fn elapsed(&self) -> Duration {
+        self.0
+    }
+}
+
+/// Records the current run.
+#[derive(Resource, Debug, Default)]
+pub struct ReplayRecorder {
+    replay: Replay,
+    /// Whether the game was paused, and its time scale, when last checked.
+    time_settings: Option<(bool, f32)>,
+}
+
+impl ReplayRecorder {
+    /// Everything recorded so far this run.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    fn record(&mut self, clock: &ReplayClock, action: ReplayAction) {
+        self.replay.events.push(ReplayEvent {
+            time: clock.0,
+            action,
+        });
+    }
+}
+
+/// Plays back a replay in place of the player.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    /// The index of the next event to play.
+    next: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, next: 0 }
+    }
+
+    /// Whether every event has been played.
+    pub fn finished(&self) -> bool {
+        self.next == self.replay.events.len()
+    }
+}
+
+fn is_playing_back(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_some()
+}
+
+fn tick_replay_clock(time: Res<Time<Virtual>>, mut clock: ResMut<ReplayClock>) {
+    clock.0 += time.delta();
+}
+
+fn start_recording(
+    mut clock: ResMut<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    rng: Res<GameRng>,
+) {
+    clock.0 = Duration::ZERO;
+    *recorder = ReplayRecorder {
+        replay: Replay {
+            seed: rng.seed(),
+            events: Vec::new(),
+        },
+        time_settings: None,
+    };
+}
+
+fn start_playback(playback: Option<ResMut<ReplayPlayback>>, mut rng: ResMut<GameRng>) {
+    let Some(mut playback) = playback else {
+        return;
+    };
+    log::info!(
+        "Playing back a replay of {} actions",
+        playback.replay.events.len()
+    );
+    playback.next = 0;
+    rng.reseed(playback.replay.seed);
+}
+
+fn save_recording(recorder: Res<ReplayRecorder>, data_dir: Res<DataDir>) {
+    data_dir.save(Replay::SAVE_KEY, &recorder.replay.to_string());
+}
+
+fn record_input(
+    input: ActionInput,
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut clicks: EventReader<Pointer<Click>>,
+    query_electron: Query<&Parent, With<Electron>>,
+    query_ring: Query<(&Ring, &Children)>,
+) {
+    if input.just_pressed(InputAction::ManualCycle) {
+        recorder.record(&clock, ReplayAction::ManualCycle);
+    }
+    for click in clicks.read() {
+        let Ok(parent) = query_electron.get(click.target) else {
+            continue;
+        };
+        let Ok((ring, children)) = query_ring.get(parent.get()) else {
+            continue;
+        };
+        let Some(electron) = children
+            .iter()
+            .filter(|child| query_electron.contains(**child))
+            .position(|child| *child == click.target)
+        else {
+            continue;
+        };
+        recorder.record(
+            &clock,
+            ReplayAction::ClickElectron {
+                ring: ring.index,
+                electron,
+            },
+        );
+    }
+}
+
+/// Forgets the time settings when returning to a run, so changes made by other screens,
+/// like the victory screen pausing the game, aren't recorded as the player's.
+fn forget_time_settings(mut recorder: ResMut<ReplayRecorder>) {
+    recorder.time_settings = None;
+}
+
+fn record_time_settings(
+    time: Res<Time<Virtual>>,
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let (paused, scale) = (time.is_paused(), time.relative_speed());
+    if let Some((was_paused, was_scale)) = recorder.time_settings {
+        if paused != was_paused {
+            recorder.record(&clock, ReplayAction::SetPaused(paused));
+        }
+        if scale != was_scale {
+            recorder.record(&clock, ReplayAction::SetTimeScale(scale));
+        }
+    }
+    recorder.time_settings = Some((paused, scale));
+}
+
+fn record_purchase(
+    trigger: Trigger<PurchaseRequested>,
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<Res<ReplayPlayback>>,
+    multiplier: Res<PurchaseMultiplier>,
+    query_ring: Query<&Ring>,
+) {
+    if playback.is_some() {
+        return;
+    }
+    let request = trigger.event();
+    let ring = request
+        .ring
+        .and_then(|ring| query_ring.get(ring).ok())
+        .map(|ring| ring.index);
+    recorder.record(
+        &clock,
+        ReplayAction::Purchase {
+            upgrade: request.upgrade.0.to_string(),
+            ring,
+            multiplier: *multiplier,
+        },
+    );
+}
+
+fn play_back(
+    mut commands: Commands,
+    clock: Res<ReplayClock>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut time: ResMut<Time<Virtual>>,
+    upgrades: Res<Upgrades>,
+    query_ring: Query<(Entity, &Ring, Option<&Children>)>,
+    mut query_controller: Query<&mut MovementController>,
+    query_electron: Query<(), With<Electron>>,
+) {
+    let find_ring = |index: usize| query_ring.iter().find(|(_, ring, _)| ring.index == index);
+
+    while let Some(event) = playback.replay.events.get(playback.next) {
+        if event.time > clock.0 {
+            break;
+        }
+        match &event.action {
+            ReplayAction::ManualCycle => {
+                for mut controller in &mut query_controller {
+                    controller.add_count = true;
+                }
+            }
+            ReplayAction::ClickElectron { ring, electron } => {
+                let clicked = find_ring(*ring)
+                    .and_then(|(_, _, children)| children)
+                    .and_then(|children| {
+                        children
+                            .iter()
+                            .filter(|child| query_electron.contains(**child))
+                            .nth(*electron)
+                    })
+                    .and_then(|electron| query_controller.get_mut(*electron).ok());
+                match clicked {
+                    Some(mut controller) => controller.add_count = true,
+                    None => log::warn!("Replay clicked missing electron {ring}.{electron}"),
+                }
+            }
+            ReplayAction::Purchase {
+                upgrade,
+                ring,
+                multiplier,
+            } => {
+                let Some(upgrade) = upgrades
+                    .iter()
+                    .map(|upgrade| upgrade.id())
+                    .find(|id| id.0 == upgrade)
+                else {
+                    log::warn!("Replay bought unknown upgrade {upgrade}");
+                    playback.next += 1;
+                    continue;
+                };
+                let request = PurchaseRequested {
+                    upgrade,
+                    // A missing ring fails the purchase, as it would have for the player.
+                    ring: ring.map(|ring| find_ring(ring).map_or(Entity::PLACEHOLDER, |r| r.0)),
+                };
+                // Each purchase is made with its own multiplier, even several in one frame,
+                // and the player's own is put back afterwards.
+                let multiplier = *multiplier;
+                commands.add(move |world: &mut World| {
+                    let previous = std::mem::replace(
+                        &mut *world.resource_mut::<PurchaseMultiplier>(),
+                        multiplier,
+                    );
+                    world.trigger(request);
+                    // Make the purchase now, while the replay's multiplier is set.
+                    world.flush_commands();
+                    *world.resource_mut::<PurchaseMultiplier>() = previous;
+                });
+            }
+            ReplayAction::SetPaused(true) => time.pause(),
+            ReplayAction::SetPaused(false) => time.unpause(),
+            ReplayAction::SetTimeScale(scale) => time.set_relative_speed(*scale),
+        }
+        playback.next += 1;
+        if playback.finished() {
+            log::info!("Replay finished");
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Ring visuals drawn by a shader.
+//!
+//! Rings spawn with a plain [`ColorMaterial`], which they swap for a [`RingMaterial`] once its
+//! shader has loaded. Without a renderer, or if the shader fails to load, they keep the plain
+//! one.
+
+use std::time::Duration;
+
+use bevy::{
+    asset::AssetLoadFailedEvent,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin},
+};
+
+use crate::{
+    game::{
+        assets::asset_path,
+        movement::{BaseTransform, RevolutionController, Revolve},
+        spawn::atom::{Electron, Ring},
+    },
+    AppConfig,
+};
+
+pub const RING_SHADER_PATH: &str = "shaders/ring.wgsl";
+/// The most electrons a ring draws trails for, as many as the largest ring holds.
+const MAX_TRAILS: usize = 8;
+const GLOW_COLOR: Color = Color::srgb(0.776, 0.847, 0.686); // #C6D8AF
+/// The ring level at which the orbit glows at about two thirds of its brightest.
+const BRIGHTNESS_LEVEL: f32 = 10.;
+/// How long a ring pulses for when its cycle timer fires.
+const PULSE_DURATION: Duration = Duration::from_millis(400);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(Material2dPlugin::<RingMaterial>::default());
+    app.init_resource::<RingShader>();
+    app.add_systems(
+        PostUpdate,
+        (
+            warn_ring_shader_failed,
+            use_ring_materials.run_if(ring_shader_loaded),
+            update_ring_materials,
+        )
+            .chain(),
+    );
+}
+
+/// The shader for [`RingMaterial`], loaded only when there is a renderer to use it.
+#[derive(Resource, Debug)]
+pub struct RingShader(pub Option<Handle<Shader>>);
+
+impl FromWorld for RingShader {
+    fn from_world(world: &mut World) -> Self {
+        let render = world.resource::<AppConfig>().render;
+        let asset_server = world.resource::<AssetServer>();
+        Self(render.then(|| asset_server.load(asset_path(RING_SHADER_PATH))))
+    }
+}
+
+/// Draws a ring's orbit as a glowing line, with a trail behind each cycling electron.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct RingMaterial {
+    #[uniform(0)]
+    pub uniform: RingUniform,
+}
+
+impl Material2d for RingMaterial {
+    fn fragment_shader() -> ShaderRef {
+        asset_path(RING_SHADER_PATH).into()
+    }
+}
+
+pub use uniform::RingUniform;
+
+// `ShaderType` checks each field's layout with functions the compiler counts as unused.
+#[allow(dead_code)]
+mod uniform {
+    use bevy::{prelude::*, render::render_resource::ShaderType};
+
+    use super::MAX_TRAILS;
+
+    /// The values [`RingMaterial`](super::RingMaterial) hands its shader.
+    #[derive(ShaderType, Debug, Clone, PartialEq, Default)]
+    pub struct RingUniform {
+        pub fill: LinearRgba,
+        pub glow: LinearRgba,
+        /// The radius of the ring's mesh, in pixels.
+        pub radius: f32,
+        /// How brightly the orbit glows, from 0 to 1.
+        pub brightness: f32,
+        /// Fades from 1 to 0 after the ring's cycle timer fires.
+        pub pulse: f32,
+        pub electron_count: u32,
+        /// Two electrons to a vector: their angle, then how far their trail reaches behind them,
+        /// both in radians.
+        pub electrons: [Vec4; MAX_TRAILS / 2],
+    }
+}
+
+fn ring_shader_loaded(shader: Res<RingShader>, asset_server: Res<AssetServer>) -> bool {
+    shader
+        .0
+        .as_ref()
+        .is_some_and(|shader| asset_server.is_loaded_with_dependencies(shader))
+}
+
+fn warn_ring_shader_failed(
+    shader: Res<RingShader>,
+    mut failures: EventReader<AssetLoadFailedEvent<Shader>>,
+) {
+    for failure in failures.read() {
+        if shader
+            .0
+            .as_ref()
+            .is_some_and(|shader| shader.id() == failure.id)
+        {
+            log::warn!(
+                "Drawing plain rings, the ring shader failed to load: {}",
+                failure.error
+            );
+        }
+    }
+}
+
+/// Swaps the plain material of each new ring for a [`RingMaterial`] in the same fill color.
+fn use_ring_materials(
+    mut commands: Commands,
+    query_ring: Query<(Entity, &Ring, &Handle<ColorMaterial>), Without<Handle<RingMaterial>>>,
+    color_materials: Res<Assets<ColorMaterial>>,
+    mut ring_materials: ResMut<Assets<RingMaterial>>,
+) {
+    for (entity, ring, color_material) in &query_ring {
+        let fill = color_materials
+            .get(color_material)
+            .map_or(Color::NONE, |material| material.color);
+        let material = ring_materials.add(RingMaterial {
+            uniform: RingUniform {
+                fill: fill.into(),
+                glow: GLOW_COLOR.into(),
+                radius: ring.radius(),
+                ..default()
+            },
+        });
+        commands
+            .entity(entity)
+            .remove::<Handle<ColorMaterial>>()
+            .insert(material);
+    }
+}
+
+fn update_ring_materials(
+    time: Res<Time>,
+    query_ring: Query<(&Ring, &Revolve, &Children, &Handle<RingMaterial>)>,
+    query_electron: Query<(&RevolutionController, &BaseTransform), With<Electron>>,
+    mut materials: ResMut<Assets<RingMaterial>>,
+) {
+    for (ring, revolve, children, handle) in &query_ring {
+        let Some(material) = materials.get(handle) else {
+            continue;
+        };
+        let mut uniform = material.uniform.clone();
+        uniform.brightness = 1. - (-(revolve.level as f32) / BRIGHTNESS_LEVEL).exp();
+
+        let fired = ring
+            .cycle_timer
+            .as_ref()
+            .is_some_and(|timer| timer.times_finished_this_tick() > 0);
+        uniform.pulse = if fired {
+            1.
+        } else {
+            (uniform.pulse - time.delta_seconds() / PULSE_DURATION.as_secs_f32()).max(0.)
+        };
+
+        uniform.electrons = default();
+        uniform.electron_count = 0;
+        let electrons = children
+            .iter()
+            .filter_map(|child| query_electron.get(*child).ok());
+        for (index, (revolution, base)) in electrons.take(MAX_TRAILS).enumerate() {
+            let start = base.0.translation.y.atan2(base.0.translation.x);
+            // Only cycling electrons leave a trail, back to where they started from.
+            let trail = if revolution.count > 0 {
+                revolution.angle()
+            } else {
+                0.
+            };
+            let pair = &mut uniform.electrons[index / 2];
+            if index % 2 == 0 {
+                (pair.x, pair.y) = (start + revolution.angle(), trail);
+            } else {
+                (pair.z, pair.w) = (start + revolution.angle(), trail);
+            }
+            uniform.electron_count += 1;
+        }
+
+        // Only touch the material when it changes, so it isn't prepared again every frame.
+        if uniform != material.uniform {
+            materials.get_mut(handle).unwrap().uniform = uniform;
+        }
+    }
+}
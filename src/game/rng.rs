@@ -0,0 +1,53 @@
+//! The game's source of randomness, seeded so runs can be repeated.
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::screen::InRun;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameRng>();
+    app.add_systems(OnEnter(InRun), restart_game_rng);
+}
+
+/// Use this instead of `rand::random` so a seeded [`AppPlugin`](crate::AppPlugin)
+/// plays out the same way every time.
+///
+/// Every run starts again from the seed, so a run can be replayed from the seed alone.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl GameRng {
+    /// Seeds from `seed`, or from a random seed if there isn't one.
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(rand::random);
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts again from `seed`.
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(Some(seed));
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+fn restart_game_rng(mut rng: ResMut<GameRng>) {
+    let seed = rng.seed;
+    rng.reseed(seed);
+}
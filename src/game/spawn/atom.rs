@@ -1,17 +1,28 @@
 //! Spawn the atom scene
 
 use crate::game::assets::{HandleMap, ImageKey};
+use crate::game::cycles::CycleSource;
 use crate::game::movement::BaseTransform;
+use crate::game::rng::GameRng;
 use crate::{
     game::movement::{MovementController, RevolutionController},
-    screen::Screen,
+    screen::InRun,
 };
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
-use rand::random;
+use rand::Rng;
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Atom>()
+        .register_type::<Ring>()
+        .register_type::<InNucleus>()
+        .register_type::<Neutron>()
+        .register_type::<Proton>()
+        .register_type::<Electron>();
+    app.init_resource::<CurrentAtom>();
     app.observe(spawn_atom_scene)
+        .observe(track_atom_added)
+        .observe(track_atom_removed)
         .observe(add_proton)
         .observe(add_proton_neutron)
         .add_systems(Update, cycle_rings);
@@ -20,10 +31,12 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Event, Debug)]
 pub struct SpawnAtomScene;
 
-#[derive(Component)]
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
 pub struct Atom;
 
-#[derive(Component)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
 pub struct Ring {
     pub index: usize,
     pub max_electrons: usize,
@@ -108,12 +121,26 @@ impl ElectronBundle {
     }
 }
 
+/// The [`Atom`] of the current run, so it can be found without searching every entity.
+#[derive(Resource, Debug, Default)]
+pub struct CurrentAtom(pub Option<Entity>);
+
+fn track_atom_added(trigger: Trigger<OnAdd, Atom>, mut current: ResMut<CurrentAtom>) {
+    current.0 = Some(trigger.entity());
+}
+
+fn track_atom_removed(trigger: Trigger<OnRemove, Atom>, mut current: ResMut<CurrentAtom>) {
+    if current.0 == Some(trigger.entity()) {
+        current.0 = None;
+    }
+}
+
 fn spawn_atom_scene(_trigger: Trigger<SpawnAtomScene>, mut commands: Commands) {
     commands.spawn((
         Atom,
         TransformBundle::default(),
         InheritedVisibility::default(),
-        StateScoped(Screen::Playing),
+        StateScoped(InRun),
     ));
 }
 
@@ -125,6 +152,7 @@ fn add_proton(
     mut commands: Commands,
     query_atom: Query<Entity, With<Atom>>,
     image_handles: Res<HandleMap<ImageKey>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let Ok(atom) = query_atom.get_single() else {
         return;
@@ -136,8 +164,8 @@ fn add_proton(
             SpriteBundle {
                 texture: image_handles[&ImageKey::Proton].clone_weak(),
                 transform: Transform::from_xyz(
-                    (random::<f32>() * 2.) - 1.,
-                    (random::<f32>() * 2.) - 1.,
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
                     0.,
                 ),
                 ..Default::default()
@@ -153,6 +181,7 @@ fn add_proton_neutron(
     mut commands: Commands,
     query_atom: Query<Entity, With<Atom>>,
     image_handles: Res<HandleMap<ImageKey>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let Ok(atom) = query_atom.get_single() else {
         return;
@@ -164,8 +193,8 @@ fn add_proton_neutron(
             SpriteBundle {
                 texture: image_handles[&ImageKey::Proton].clone_weak(),
                 transform: Transform::from_xyz(
-                    (random::<f32>() * 2.) - 1.,
-                    (random::<f32>() * 2.) - 1.,
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
                     0.,
                 ),
                 ..Default::default()
@@ -177,8 +206,8 @@ fn add_proton_neutron(
             SpriteBundle {
                 texture: image_handles[&ImageKey::Neutron].clone_weak(),
                 transform: Transform::from_xyz(
-                    (random::<f32>() * 2.) - 1.,
-                    (random::<f32>() * 2.) - 1.,
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
                     0.,
                 ),
                 ..Default::default()
@@ -198,7 +227,7 @@ fn cycle_rings(
             if timer.finished() {
                 for child in children.iter() {
                     if let Ok(mut r) = query_electrons.get_mut(*child) {
-                        r.add_count();
+                        r.add_count(CycleSource::Automatic);
                     }
                 }
             }
@@ -0,0 +1,210 @@
+//! Track how many cycles are being earned over time
+
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::cycles::{AddCycle, CycleSource};
+use crate::game::upgrades::PurchaseCompleted;
+use crate::screen::{InRun, Screen};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CycleStats>();
+    app.init_resource::<CycleStats>();
+    app.add_systems(OnEnter(InRun), reset_cycle_stats);
+    app.observe(record_cycle)
+        .observe(record_purchase)
+        .add_systems(
+            Update,
+            (
+                tick_cycle_stats.in_set(AppSet::TickTimers),
+                sample_cycle_stats.in_set(AppSet::Update),
+            )
+                .run_if(in_state(Screen::Playing)),
+        );
+}
+
+/// The sliding windows, in seconds, that rates are reported over.
+pub const STAT_WINDOWS: [f32; 3] = [1., 10., 60.];
+
+/// How often the rate history used by the graph is sampled.
+const SAMPLE_INTERVAL_SECS: f32 = 0.5;
+
+/// How many samples are kept for the graph.
+pub const HISTORY_LEN: usize = 120;
+
+/// The window, in seconds, that graph samples are averaged over.
+const HISTORY_WINDOW_SECS: f32 = 10.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+struct CycleRecord {
+    /// Seconds since the stats were reset.
+    time: f32,
+    ring: usize,
+    source: CycleSource,
+}
+
+/// Cycles earned recently, used to report cycles-per-second.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CycleStats {
+    /// Seconds since the stats were reset.
+    elapsed: f32,
+    /// Every cycle earned within the largest window in [`STAT_WINDOWS`].
+    records: VecDeque<CycleRecord>,
+    /// Cycles-per-second averaged over [`HISTORY_WINDOW_SECS`], oldest first.
+    history: VecDeque<f32>,
+    sample_timer: Timer,
+    /// Cycles earned since the stats were reset.
+    total: u64,
+    /// Upgrade purchases made since the stats were reset.
+    purchases: u32,
+    /// Cycles spent on upgrades since the stats were reset.
+    spent: u64,
+}
+
+impl Default for CycleStats {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.,
+            records: VecDeque::new(),
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            sample_timer: Timer::from_seconds(SAMPLE_INTERVAL_SECS, TimerMode::Repeating),
+            total: 0,
+            purchases: 0,
+            spent: 0,
+        }
+    }
+}
+
+impl CycleStats {
+    /// Cycles-per-second over the last `window` seconds.
+    pub fn rate(&self, window: f32) -> f32 {
+        self.rate_filtered(window, |_| true)
+    }
+
+    /// Cycles-per-second over the last `window` seconds from electrons on `ring`.
+    pub fn ring_rate(&self, window: f32, ring: usize) -> f32 {
+        self.rate_filtered(window, |record| record.ring == ring)
+    }
+
+    /// Cycles-per-second over the last `window` seconds from `source`.
+    pub fn source_rate(&self, window: f32, source: CycleSource) -> f32 {
+        self.rate_filtered(window, |record| record.source == source)
+    }
+
+    /// The indices of every ring that has earned a cycle within the largest window.
+    pub fn rings(&self) -> Vec<usize> {
+        let mut rings: Vec<usize> = self.records.iter().map(|record| record.ring).collect();
+        rings.sort_unstable();
+        rings.dedup();
+        rings
+    }
+
+    /// Sampled cycles-per-second, oldest first.
+    pub fn history(&self) -> impl ExactSizeIterator<Item = f32> + '_ {
+        self.history.iter().copied()
+    }
+
+    /// Seconds since the stats were reset.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Cycles earned since the stats were reset.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Upgrade purchases made since the stats were reset.
+    pub fn purchases(&self) -> u32 {
+        self.purchases
+    }
+
+    /// Cycles spent on upgrades since the stats were reset.
+    pub fn spent(&self) -> u64 {
+        self.spent
+    }
+
+    fn rate_filtered(&self, window: f32, filter: impl Fn(&CycleRecord) -> bool) -> f32 {
+        // Don't under-report before a full window has passed.
+        let window = window.min(self.elapsed).max(1.);
+        let since = self.elapsed - window;
+        let count = self
+            .records
+            .iter()
+            .rev()
+            .take_while(|record| record.time > since)
+            .filter(|record| filter(record))
+            .count();
+        count as f32 / window
+    }
+}
+
+/// Revolutions per minute of an electron orbiting at `speed` radians per second.
+pub fn revolutions_per_minute(speed: f32) -> f32 {
+    speed * 60. / (2. * PI)
+}
+
+/// The cycles-per-second a ring earns from its cycle timer alone, once every electron is orbiting.
+pub fn projected_ring_rate(electrons: usize, speed: f32, cycle_duration: Option<Duration>) -> f32 {
+    let Some(interval) = cycle_duration.map(|duration| duration.as_secs_f32()) else {
+        return 0.;
+    };
+    if speed <= 0. || interval <= 0. {
+        return 0.;
+    }
+    // An electron that is still revolving when the timer fires misses that tick.
+    let revolution = 2. * PI / speed;
+    let period = (revolution / interval).ceil() * interval;
+    electrons as f32 / period
+}
+
+fn reset_cycle_stats(mut stats: ResMut<CycleStats>) {
+    *stats = CycleStats::default();
+}
+
+fn record_cycle(trigger: Trigger<AddCycle>, mut stats: ResMut<CycleStats>) {
+    let event = trigger.event();
+    let time = stats.elapsed;
+    stats.total += 1;
+    stats.records.push_back(CycleRecord {
+        time,
+        ring: event.ring,
+        source: event.source,
+    });
+}
+
+fn record_purchase(trigger: Trigger<PurchaseCompleted>, mut stats: ResMut<CycleStats>) {
+    stats.purchases += 1;
+    stats.spent += u64::from(trigger.event().cost);
+}
+
+fn tick_cycle_stats(time: Res<Time>, mut stats: ResMut<CycleStats>) {
+    stats.elapsed += time.delta_seconds();
+    stats.sample_timer.tick(time.delta());
+
+    let max_window = STAT_WINDOWS[STAT_WINDOWS.len() - 1];
+    let since = stats.elapsed - max_window;
+    while stats
+        .records
+        .front()
+        .is_some_and(|record| record.time <= since)
+    {
+        stats.records.pop_front();
+    }
+}
+
+fn sample_cycle_stats(mut stats: ResMut<CycleStats>) {
+    if !stats.sample_timer.just_finished() {
+        return;
+    }
+    let rate = stats.rate(HISTORY_WINDOW_SECS);
+    if stats.history.len() == HISTORY_LEN {
+        stats.history.pop_front();
+    }
+    stats.history.push_back(rate);
+}
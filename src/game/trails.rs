@@ -0,0 +1,207 @@
+//! Motion trails behind revolving electrons.
+//!
+//! Each [`Electron`] remembers where it has been in an [`ElectronTrail`]. Every trail is drawn
+//! into one shared ribbon mesh, so dozens of electrons still cost a single draw. The player can
+//! turn trails off in [`Settings`].
+
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+        view::NoFrustumCulling,
+    },
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use crate::{
+    game::{
+        movement::Revolve,
+        spawn::atom::{Atom, Electron, Ring},
+    },
+    settings::Settings,
+};
+
+/// How far back a trail reaches, in seconds of movement. Faster electrons cover more of their
+/// orbit in that time, so their trails are longer.
+const TRAIL_DURATION: f32 = 0.15;
+/// The longest a trail gets, in radians around its orbit, however fast its electron is.
+const MAX_TRAIL_ANGLE: f32 = 2.5;
+/// The width of a trail where it meets its electron, in pixels.
+const TRAIL_WIDTH: f32 = 10.;
+const TRAIL_ALPHA: f32 = 0.6;
+/// Trail colors by ring, repeating for rings past the last.
+const TRAIL_COLORS: [Color; 4] = [
+    Color::srgb(0.776, 0.847, 0.686), // #C6D8AF
+    Color::srgb(0.925, 0.451, 0.341), // #EC7357
+    Color::srgb(0.867, 0.827, 0.412), // #DDD369
+    Color::srgb(0.925, 0.925, 0.925), // #ECECEC
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ElectronTrail>();
+    app.observe(add_electron_trail).observe(spawn_trail_mesh);
+    app.add_systems(
+        PostUpdate,
+        (record_electron_trails, build_trail_mesh).chain(),
+    );
+}
+
+/// Where an electron has been recently, from oldest to newest, with the time it was there.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ElectronTrail {
+    points: VecDeque<(f32, Vec2)>,
+}
+
+impl ElectronTrail {
+    /// The length of the trail, in pixels.
+    pub fn length(&self) -> f32 {
+        self.points
+            .iter()
+            .zip(self.points.iter().skip(1))
+            .map(|((_, a), (_, b))| a.distance(*b))
+            .sum()
+    }
+}
+
+/// The mesh every [`ElectronTrail`] is drawn into.
+#[derive(Component, Debug)]
+pub struct TrailMesh;
+
+fn add_electron_trail(trigger: Trigger<OnAdd, Electron>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .insert(ElectronTrail::default());
+}
+
+fn spawn_trail_mesh(
+    trigger: Trigger<OnAdd, Atom>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.entity(trigger.entity()).with_children(|parent| {
+        parent.spawn((
+            Name::new("Electron Trails"),
+            TrailMesh,
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(trail_mesh(Vec::new(), Vec::new(), Vec::new()))),
+                // White, so the trails keep their own vertex colors.
+                material: materials.add(Color::WHITE),
+                // Above the rings, below the electrons.
+                transform: Transform::from_xyz(0., 0., 5.),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            // The mesh changes every frame, so its bounds from when it was spawned mean nothing.
+            NoFrustumCulling,
+        ));
+    });
+}
+
+fn record_electron_trails(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query_electron: Query<(&Parent, &Transform, &mut ElectronTrail)>,
+    query_ring: Query<&Revolve>,
+) {
+    let now = time.elapsed_seconds();
+    for (parent, transform, mut trail) in &mut query_electron {
+        if !settings.electron_trails {
+            trail.points.clear();
+            continue;
+        }
+        // Rings sit at the center of the atom, so this is where the electron is in the atom.
+        let position = transform.translation.truncate();
+        if trail
+            .points
+            .back()
+            .is_none_or(|(_, last)| last.distance(position) > 0.5)
+        {
+            trail.points.push_back((now, position));
+        }
+
+        let speed = query_ring.get(parent.get()).map_or(0., Revolve::speed);
+        let duration = TRAIL_DURATION.min(MAX_TRAIL_ANGLE / speed.max(f32::EPSILON));
+        while trail
+            .points
+            .front()
+            .is_some_and(|(time, _)| now - time > duration)
+        {
+            trail.points.pop_front();
+        }
+    }
+}
+
+/// Rebuilds the trail mesh as a ribbon along each trail, narrowing and fading towards its end.
+fn build_trail_mesh(
+    settings: Res<Settings>,
+    query_electron: Query<(&Parent, &ElectronTrail)>,
+    query_ring: Query<&Ring>,
+    mut query_mesh: Query<(&Mesh2dHandle, &mut Visibility), With<TrailMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Ok((handle, mut visibility)) = query_mesh.get_single_mut() else {
+        return;
+    };
+
+    let mut vertices = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    if settings.electron_trails {
+        for (parent, trail) in &query_electron {
+            let points = &trail.points;
+            if points.len() < 2 {
+                continue;
+            }
+            let index = query_ring.get(parent.get()).map_or(0, |ring| ring.index);
+            let color = LinearRgba::from(TRAIL_COLORS[index % TRAIL_COLORS.len()]);
+            let first = vertices.len() as u32;
+            let last = points.len() - 1;
+            for (i, (_, point)) in points.iter().enumerate() {
+                let before = points[i.saturating_sub(1)].1;
+                let after = points[(i + 1).min(last)].1;
+                let normal = (after - before).perp().normalize_or_zero();
+                // 0 at the end of the trail, 1 at the electron.
+                let t = i as f32 / last as f32;
+                let offset = normal * TRAIL_WIDTH * t / 2.;
+                let color = color.with_alpha(TRAIL_ALPHA * t * t).to_f32_array();
+                vertices.extend([(*point + offset).extend(0.), (*point - offset).extend(0.)]);
+                colors.extend([color, color]);
+            }
+            // Two triangles between each point and the next.
+            for i in 0..last as u32 {
+                let [a, b, c, d] = [0, 1, 2, 3].map(|corner| first + i * 2 + corner);
+                indices.extend([a, b, c, b, d, c]);
+            }
+        }
+    }
+
+    let visible = !vertices.is_empty();
+    if let Some(mesh) = meshes.get_mut(&handle.0) {
+        if visible {
+            *mesh = trail_mesh(vertices, colors, indices);
+        }
+    }
+    let target = if visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != target {
+        *visibility = target;
+    }
+}
+
+fn trail_mesh(vertices: Vec<Vec3>, colors: Vec<[f32; 4]>, indices: Vec<u32>) -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vertices)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices))
+}
@@ -0,0 +1,176 @@
+//! Walks new players through their first purchases.
+//!
+//! The tutorial is a list of [`TutorialStep`]s. Each step points at something on screen and
+//! advances when its [`StepGoal`] happens. Finishing or skipping the tutorial is remembered in
+//! [`Settings`], so it is only shown once.
+
+use bevy::prelude::*;
+
+use crate::game::cycles::{AddCycle, CycleSource};
+use crate::game::upgrades::{
+    BuyElectron, BuyNextRing, CycleUpgrade, PurchaseCompleted, Upgrade, UpgradeId,
+};
+use crate::input::{BindingKind, InputAction, InputBindings};
+use crate::screen::InRun;
+use crate::settings::Settings;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Tutorial>();
+    app.add_systems(OnEnter(InRun), start_tutorial);
+    app.observe(advance_on_purchase)
+        .observe(advance_on_cycle)
+        .observe(finish_tutorial);
+}
+
+/// What a tutorial step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepTarget {
+    /// The first button that buys this upgrade.
+    UpgradeButton(UpgradeId),
+    /// The first electron.
+    Electron,
+}
+
+/// What the player has to do to finish a tutorial step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepGoal {
+    Purchase(UpgradeId),
+    ManualCycle,
+}
+
+#[derive(Debug, Clone)]
+pub struct TutorialStep {
+    /// Shown to the player, with `{spin}` replaced by the input that spins electrons.
+    pub text: &'static str,
+    pub target: StepTarget,
+    pub goal: StepGoal,
+}
+
+impl TutorialStep {
+    /// The step's text for the player's current bindings.
+    pub(crate) fn describe(&self, bindings: &InputBindings) -> String {
+        let spin = bindings.get(InputAction::ManualCycle);
+        let spin = spin
+            .iter()
+            .find(|binding| binding.kind() == BindingKind::KeyboardMouse)
+            .or(spin.first())
+            .map_or_else(
+                || format!("the {} key", InputAction::ManualCycle.name()),
+                ToString::to_string,
+            );
+        self.text.replace("{spin}", &spin)
+    }
+}
+
+pub fn tutorial_steps() -> Vec<TutorialStep> {
+    vec![
+        TutorialStep {
+            text: "Your atom needs a ring for electrons to orbit. The first one is free!",
+            target: StepTarget::UpgradeButton(BuyNextRing.id()),
+            goal: StepGoal::Purchase(BuyNextRing.id()),
+        },
+        TutorialStep {
+            text: "Add an electron to the ring.",
+            target: StepTarget::UpgradeButton(BuyElectron.id()),
+            goal: StepGoal::Purchase(BuyElectron.id()),
+        },
+        TutorialStep {
+            text: "Click an electron, or press {spin}, to spin it. Every revolution earns a cycle.",
+            target: StepTarget::Electron,
+            goal: StepGoal::ManualCycle,
+        },
+        TutorialStep {
+            text: "Spend cycles on a cycle timer to keep the ring spinning on its own.",
+            target: StepTarget::UpgradeButton(CycleUpgrade.id()),
+            goal: StepGoal::Purchase(CycleUpgrade.id()),
+        },
+    ]
+}
+
+/// The tutorial's progress within the current game.
+#[derive(Resource, Debug, Clone)]
+pub struct Tutorial {
+    steps: Vec<TutorialStep>,
+    /// Index of the current step, or `None` if the tutorial isn't running.
+    current: Option<usize>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            steps: tutorial_steps(),
+            current: None,
+        }
+    }
+}
+
+impl Tutorial {
+    pub fn current_step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.current?)
+    }
+
+    /// The current step's number, counting from one, and the number of steps.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        Some((self.current? + 1, self.steps.len()))
+    }
+
+    fn complete(&mut self, goal: StepGoal) -> bool {
+        if self.current_step().map(|step| step.goal) != Some(goal) {
+            return false;
+        }
+        self.current = self.current.map(|current| current + 1);
+        true
+    }
+}
+
+/// Trigger this event to end the tutorial, whether it was finished or skipped.
+#[derive(Event, Debug)]
+pub struct FinishTutorial;
+
+fn start_tutorial(mut tutorial: ResMut<Tutorial>, settings: Res<Settings>) {
+    tutorial.current = if settings.tutorial_complete {
+        None
+    } else {
+        Some(0)
+    };
+}
+
+fn advance(commands: &mut Commands, tutorial: &mut Tutorial, goal: StepGoal) {
+    if tutorial.complete(goal) && tutorial.current_step().is_none() {
+        commands.trigger(FinishTutorial);
+    }
+}
+
+fn advance_on_purchase(
+    trigger: Trigger<PurchaseCompleted>,
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    advance(
+        &mut commands,
+        &mut tutorial,
+        StepGoal::Purchase(trigger.event().upgrade),
+    );
+}
+
+fn advance_on_cycle(
+    trigger: Trigger<AddCycle>,
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    if trigger.event().source == CycleSource::Manual {
+        advance(&mut commands, &mut tutorial, StepGoal::ManualCycle);
+    }
+}
+
+fn finish_tutorial(
+    _trigger: Trigger<FinishTutorial>,
+    mut tutorial: ResMut<Tutorial>,
+    mut settings: ResMut<Settings>,
+) {
+    tutorial.current = None;
+    if !settings.tutorial_complete {
+        log::info!("Tutorial complete");
+        settings.tutorial_complete = true;
+    }
+}
@@ -2,8 +2,12 @@
 
 use bevy::prelude::*;
 
-use crate::screen::Screen;
-use crate::{game::cycles::CycleCount, ui::palette::BUTTON_TEXT};
+use crate::game::stats::{CycleStats, STAT_WINDOWS};
+use crate::screen::InRun;
+use crate::{
+    game::cycles::CycleCount,
+    ui::palette::{BUTTON_TEXT, LABEL_TEXT},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.observe(spawn_cycle_ui);
@@ -34,6 +38,11 @@ fn spawn_cycle_ui(_trigger: Trigger<SpawnCycleUi>, mut commands: Commands) {
                 color: BUTTON_TEXT,
                 ..default()
             }),
+            TextSection::from_style(TextStyle {
+                font_size: 24.0,
+                color: LABEL_TEXT,
+                ..default()
+            }),
         ]) // Set the justification of the Text
         .with_text_justify(JustifyText::Center)
         // Set the style of the TextBundle itself.
@@ -44,7 +53,7 @@ fn spawn_cycle_ui(_trigger: Trigger<SpawnCycleUi>, mut commands: Commands) {
             ..default()
         }),
         CycleCountText,
-        StateScoped(Screen::Playing),
+        StateScoped(InRun),
     ));
 }
 
@@ -53,9 +62,11 @@ pub struct CycleCountText;
 
 fn update_cycle_count_text(
     count: Res<CycleCount>,
+    stats: Res<CycleStats>,
     mut query: Query<&mut Text, With<CycleCountText>>,
 ) {
     for mut text in &mut query {
         text.sections[1].value = format!("{}", count.0);
+        text.sections[2].value = format!("  {:.1}/s", stats.rate(STAT_WINDOWS[1]));
     }
 }
@@ -3,11 +3,25 @@
 use bevy::prelude::*;
 mod atom_label;
 mod cycle_ui;
+mod notifications;
+pub(crate) mod ring_inspector;
+pub(crate) mod stats_ui;
+mod transaction_log;
+mod tutorial_ui;
 pub(crate) mod upgrades;
 
 pub(super) fn plugin(app: &mut App) {
     app.observe(spawn_level_ui);
-    app.add_plugins((cycle_ui::plugin, upgrades::plugin, atom_label::plugin));
+    app.add_plugins((
+        cycle_ui::plugin,
+        stats_ui::plugin,
+        transaction_log::plugin,
+        ring_inspector::plugin,
+        upgrades::plugin,
+        atom_label::plugin,
+        notifications::plugin,
+        tutorial_ui::plugin,
+    ));
 }
 
 #[derive(Event, Debug)]
@@ -17,4 +31,8 @@ fn spawn_level_ui(_trigger: Trigger<SpawnLevelUi>, mut commands: Commands) {
     commands.trigger(cycle_ui::SpawnCycleUi);
     commands.trigger(upgrades::SpawnUpgradesUi);
     commands.trigger(atom_label::SpawnAtomLabel);
+    commands.trigger(stats_ui::SpawnStatsUi);
+    commands.trigger(transaction_log::SpawnTransactionLogUi);
+    commands.trigger(ring_inspector::SpawnRingInspector);
+    commands.trigger(tutorial_ui::SpawnTutorialUi);
 }
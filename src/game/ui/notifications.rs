@@ -0,0 +1,81 @@
+//! Turns game events into toasts
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::achievements::AchievementUnlocked;
+use crate::game::assets::{HandleMap, ImageAtlases, ImageKey};
+use crate::game::upgrades::{PurchaseFailed, PurchaseFailure, Upgrades};
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(notify_achievement_unlocked)
+        .observe(notify_purchase_failed);
+}
+
+fn notify_achievement_unlocked(
+    trigger: Trigger<AchievementUnlocked>,
+    mut commands: Commands,
+    image_handles: Res<HandleMap<ImageKey>>,
+    atlases: Res<ImageAtlases>,
+) {
+    let achievement = trigger.event().0;
+    let toast = Toast::new(format!("Achievement unlocked: {}", achievement.name))
+        .with_body(achievement.description)
+        .with_priority(ToastPriority::High);
+    commands.trigger(with_icon(toast, ImageKey::Proton, &image_handles, &atlases));
+}
+
+fn notify_purchase_failed(
+    trigger: Trigger<PurchaseFailed>,
+    mut commands: Commands,
+    upgrades: Res<Upgrades>,
+    image_handles: Res<HandleMap<ImageKey>>,
+    atlases: Res<ImageAtlases>,
+) {
+    let event = trigger.event();
+    let Some(upgrade) = upgrades.get(event.upgrade) else {
+        return;
+    };
+    let (title, body) = match (event.reason, event.cost) {
+        (PurchaseFailure::NotEnoughCycles, Some(cost)) => (
+            format!("Cannot afford {}", upgrade.name()),
+            format!("Needs {} cycles", cost),
+        ),
+        (PurchaseFailure::Maxed, _) => {
+            (upgrade.name().to_string(), upgrade.maxed_text().to_string())
+        }
+        (reason, _) => (
+            format!("Cannot buy {}", upgrade.name()),
+            reason.description().to_string(),
+        ),
+    };
+    let toast = Toast::new(title)
+        .with_body(body)
+        .with_priority(ToastPriority::Low)
+        .with_duration(Duration::from_secs(2))
+        .scoped(Screen::Playing);
+    commands.trigger(with_icon(
+        toast,
+        ImageKey::Electron,
+        &image_handles,
+        &atlases,
+    ));
+}
+
+/// Gives `toast` the image of `key` as its icon, showing only its first frame if the image is a
+/// sprite sheet.
+fn with_icon(
+    toast: Toast,
+    key: ImageKey,
+    image_handles: &HandleMap<ImageKey>,
+    atlases: &ImageAtlases,
+) -> Toast {
+    let toast = toast.with_icon(image_handles[&key].clone_weak());
+    match atlases.sprite(key) {
+        Some((atlas, _)) => toast.with_icon_atlas(atlas),
+        None => toast,
+    }
+}
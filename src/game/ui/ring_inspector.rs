@@ -0,0 +1,267 @@
+//! Inspect a single ring's production and upgrades
+
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::game::movement::Revolve;
+use crate::game::spawn::atom::{Electron, Ring};
+use crate::game::stats::{projected_ring_rate, revolutions_per_minute, CycleStats, STAT_WINDOWS};
+use crate::game::upgrades::costs::{compute_cycle_cost, compute_electron_cost, compute_speed_cost};
+use crate::game::upgrades::{next_cycle_duration, SPEED_UPGRADE_INCREMENT};
+use crate::screen::{InRun, Screen};
+use crate::ui::palette::{BUTTON_TEXT, HEADER_TEXT, NODE_BACKGROUND};
+use crate::ui::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InspectedRing>();
+    app.add_systems(OnEnter(InRun), clear_inspected_ring);
+    app.observe(spawn_ring_inspector).add_systems(
+        Update,
+        (
+            (inspect_clicked_ring, handle_inspector_action),
+            update_ring_inspector,
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnRingInspector;
+
+/// The ring currently shown in the inspector, if any.
+#[derive(Resource, Debug, Default)]
+pub struct InspectedRing(pub Option<Entity>);
+
+/// Pressing a button with this component opens the inspector for the ring.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InspectRing(pub Entity);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum InspectorAction {
+    Close,
+}
+
+#[derive(Component)]
+struct RingInspector;
+
+#[derive(Component)]
+struct RingInspectorTitle;
+
+#[derive(Component)]
+struct RingInspectorText;
+
+fn spawn_ring_inspector(_trigger: Trigger<SpawnRingInspector>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Ring Inspector"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    bottom: Val::Px(60.0),
+                    right: Val::Px(5.0),
+                    width: Val::Px(300.),
+                    padding: UiRect::all(Val::Px(5.)),
+                    row_gap: Val::Px(5.),
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+            RingInspector,
+            StateScoped(InRun),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 24.,
+                                color: HEADER_TEXT,
+                                ..default()
+                            },
+                        ),
+                        RingInspectorTitle,
+                        Label,
+                    ));
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::horizontal(Val::Px(8.)),
+                                    ..default()
+                                },
+                                background_color: BackgroundColor(NODE_BACKGROUND),
+                                ..default()
+                            },
+                            InteractionPalette {
+                                none: NODE_BACKGROUND,
+                                hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+                                pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+                            },
+                            InspectorAction::Close,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "x",
+                                TextStyle {
+                                    font_size: 24.,
+                                    color: BUTTON_TEXT,
+                                    ..default()
+                                },
+                            ));
+                        });
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 18.,
+                        color: BUTTON_TEXT,
+                        ..default()
+                    },
+                ),
+                RingInspectorText,
+                Label,
+            ));
+        });
+}
+
+fn clear_inspected_ring(mut inspected: ResMut<InspectedRing>) {
+    inspected.0 = None;
+}
+
+fn inspect_clicked_ring(
+    mut clicks: EventReader<Pointer<Click>>,
+    query_ring: Query<(), With<Ring>>,
+    mut inspected: ResMut<InspectedRing>,
+) {
+    for click in clicks.read() {
+        if query_ring.contains(click.target) {
+            inspected.0 = Some(click.target);
+        }
+    }
+}
+
+fn handle_inspector_action(
+    mut inspected: ResMut<InspectedRing>,
+    query_inspect: InteractionQuery<&InspectRing>,
+    query_action: InteractionQuery<&InspectorAction>,
+) {
+    for (interaction, inspect) in &query_inspect {
+        if matches!(interaction, Interaction::Pressed) {
+            inspected.0 = Some(inspect.0);
+        }
+    }
+    for (interaction, action) in &query_action {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                InspectorAction::Close => inspected.0 = None,
+            }
+        }
+    }
+}
+
+fn update_ring_inspector(
+    inspected: Res<InspectedRing>,
+    stats: Res<CycleStats>,
+    query_ring: Query<(&Ring, &Revolve, Option<&Children>)>,
+    query_electrons: Query<(), With<Electron>>,
+    mut query_inspector: Query<&mut Style, With<RingInspector>>,
+    mut query_title: Query<&mut Text, (With<RingInspectorTitle>, Without<RingInspectorText>)>,
+    mut query_text: Query<&mut Text, (With<RingInspectorText>, Without<RingInspectorTitle>)>,
+) {
+    let ring = inspected.0.and_then(|entity| query_ring.get(entity).ok());
+    for mut style in &mut query_inspector {
+        style.display = if ring.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    let Some((ring, revolve, children)) = ring else {
+        return;
+    };
+
+    let electrons = children
+        .map(|children| {
+            children
+                .iter()
+                .filter(|child| query_electrons.contains(**child))
+                .count()
+        })
+        .unwrap_or_default();
+    let duration = ring.cycle_timer.as_ref().map(Timer::duration);
+    let projected = projected_ring_rate(electrons, revolve.speed(), duration);
+
+    let mut value = format!(
+        "Electrons: {}/{}\nSpeed: {:.2} (level {})\nRevolutions: {:.1}/min",
+        electrons,
+        ring.max_electrons,
+        revolve.speed(),
+        revolve.level,
+        revolutions_per_minute(revolve.speed()),
+    );
+    value += &match ring.cycle_timer.as_ref() {
+        Some(timer) => format!(
+            "\nCycle timer: {:.2}s ({:.0}%)",
+            timer.duration().as_secs_f32(),
+            timer.fraction() * 100.,
+        ),
+        None => "\nCycle timer: none".to_string(),
+    };
+    value += &format!(
+        "\nEarning: {:.2}/s\nProjected: {:.2}/s",
+        stats.ring_rate(STAT_WINDOWS[1], ring.index),
+        projected,
+    );
+
+    value += "\n\nNext purchase";
+    if electrons < ring.max_electrons {
+        value += &format!(
+            "\nElectron: +{:.2}/s for {}",
+            projected_ring_rate(electrons + 1, revolve.speed(), duration) - projected,
+            compute_electron_cost(ring.index, electrons),
+        );
+    } else {
+        value += "\nElectron: Full";
+    }
+    value += &format!(
+        "\nSpeed: +{:.2}/s for {}",
+        projected_ring_rate(
+            electrons,
+            revolve.speed() + SPEED_UPGRADE_INCREMENT,
+            duration
+        ) - projected,
+        compute_speed_cost(ring.index, revolve.level),
+    );
+    value += &format!(
+        "\nCycles: +{:.2}/s for {}",
+        projected_ring_rate(
+            electrons,
+            revolve.speed(),
+            Some(next_cycle_duration(duration))
+        ) - projected,
+        compute_cycle_cost(ring.index, duration),
+    );
+
+    for mut text in &mut query_title {
+        text.sections[0].value = format!("Ring {}", ring.index + 1);
+    }
+    for mut text in &mut query_text {
+        text.sections[0].value.clone_from(&value);
+    }
+}
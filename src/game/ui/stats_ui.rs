@@ -0,0 +1,148 @@
+//! Displays cycles-per-second statistics and a graph of recent rates
+
+use bevy::prelude::*;
+
+use crate::game::cycles::CycleSource;
+use crate::game::stats::{CycleStats, HISTORY_LEN, STAT_WINDOWS};
+use crate::screen::{InRun, Screen};
+use crate::ui::palette::{BUTTON_TEXT, LABEL_TEXT, NODE_BACKGROUND};
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(spawn_stats_ui);
+    app.add_systems(
+        Update,
+        (update_stats_text, draw_stats_graph).run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnStatsUi;
+
+#[derive(Component)]
+struct StatsText;
+
+/// The area the rate history is drawn into.
+/// Gizmos are drawn beneath the UI, so this node must not have a background.
+#[derive(Component)]
+struct StatsGraph;
+
+/// The width of the stats panel along the right of the screen, in pixels.
+pub const STATS_PANEL_WIDTH: f32 = 260.;
+/// The gap between the stats panel and the edges of the screen.
+pub const STATS_PANEL_MARGIN: f32 = 5.;
+const GRAPH_HEIGHT: f32 = 100.;
+
+fn spawn_stats_ui(_trigger: Trigger<SpawnStatsUi>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Stats Panel"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    top: Val::Px(STATS_PANEL_MARGIN),
+                    right: Val::Px(STATS_PANEL_MARGIN),
+                    width: Val::Px(STATS_PANEL_WIDTH),
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(InRun),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(5.)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(NODE_BACKGROUND),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 18.,
+                                color: BUTTON_TEXT,
+                                ..default()
+                            },
+                        ),
+                        StatsText,
+                        Label,
+                    ));
+                });
+            parent.spawn((
+                Name::new("Stats Graph"),
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Px(GRAPH_HEIGHT),
+                        border: UiRect::all(Val::Px(2.)),
+                        ..default()
+                    },
+                    border_color: BorderColor(NODE_BACKGROUND),
+                    ..default()
+                },
+                StatsGraph,
+            ));
+        });
+}
+
+fn update_stats_text(stats: Res<CycleStats>, mut query: Query<&mut Text, With<StatsText>>) {
+    let mut value = String::from("Cycles per second");
+    for window in STAT_WINDOWS {
+        value += &format!("\n{}s: {:.2}", window, stats.rate(window));
+    }
+
+    let window = STAT_WINDOWS[1];
+    value += &format!(
+        "\nManual: {:.2}\nAutomatic: {:.2}",
+        stats.source_rate(window, CycleSource::Manual),
+        stats.source_rate(window, CycleSource::Automatic),
+    );
+    for ring in stats.rings() {
+        value += &format!("\nRing {}: {:.2}", ring + 1, stats.ring_rate(window, ring));
+    }
+    value += &format!(
+        "\nPurchases: {}\nSpent: {}",
+        stats.purchases(),
+        stats.spent()
+    );
+
+    for mut text in &mut query {
+        text.sections[0].value.clone_from(&value);
+    }
+}
+
+fn draw_stats_graph(
+    stats: Res<CycleStats>,
+    query_graph: Query<(&Node, &GlobalTransform), With<StatsGraph>>,
+    query_camera: Query<(&Camera, &GlobalTransform), With<IsDefaultUiCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((camera, camera_transform)) = query_camera.get_single() else {
+        return;
+    };
+
+    let peak = stats.history().fold(1., f32::max);
+    for (node, transform) in &query_graph {
+        // UI nodes are positioned by their centre in viewport coordinates.
+        let size = node.size();
+        let bottom_left = transform.translation().truncate() + Vec2::new(-size.x, size.y) / 2.;
+
+        let points: Vec<Vec2> = stats
+            .history()
+            .enumerate()
+            .filter_map(|(i, rate)| {
+                let x = size.x * i as f32 / (HISTORY_LEN - 1) as f32;
+                let y = size.y * rate / peak;
+                camera.viewport_to_world_2d(camera_transform, bottom_left + Vec2::new(x, -y))
+            })
+            .collect();
+
+        gizmos.linestrip_2d(points, LABEL_TEXT);
+    }
+}
@@ -0,0 +1,212 @@
+//! Displays the most recent purchases
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::replay::ReplayClock;
+use crate::game::upgrades::transactions::{Transaction, TransactionLog};
+use crate::screen::{InRun, Screen};
+use crate::ui::palette::{BUTTON_DISABLED_TEXT, BUTTON_TEXT, HEADER_TEXT, NODE_BACKGROUND};
+use crate::ui::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(spawn_transaction_log_ui);
+    app.add_systems(
+        Update,
+        (
+            handle_log_action,
+            update_transaction_log_text.run_if(transaction_log_visible),
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnTransactionLogUi;
+
+/// How many transactions are shown, newest first.
+const SHOWN_TRANSACTIONS: usize = 12;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum LogAction {
+    Toggle,
+}
+
+/// Holds the entries, hidden until the header is pressed.
+#[derive(Component)]
+struct TransactionLogBody;
+
+#[derive(Component)]
+struct TransactionLogText;
+
+fn spawn_transaction_log_ui(_trigger: Trigger<SpawnTransactionLogUi>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Transaction Log"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    top: Val::Px(5.0),
+                    right: Val::Px(270.0),
+                    width: Val::Px(280.),
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(InRun),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(5.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(NODE_BACKGROUND),
+                        ..default()
+                    },
+                    InteractionPalette {
+                        none: NODE_BACKGROUND,
+                        hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+                        pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+                    },
+                    LogAction::Toggle,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Transactions",
+                        TextStyle {
+                            font_size: 20.,
+                            color: HEADER_TEXT,
+                            ..default()
+                        },
+                    ));
+                });
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            display: Display::None,
+                            padding: UiRect::all(Val::Px(5.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(NODE_BACKGROUND),
+                        ..default()
+                    },
+                    TransactionLogBody,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 16.,
+                                color: BUTTON_TEXT,
+                                ..default()
+                            },
+                        ),
+                        TransactionLogText,
+                        Label,
+                    ));
+                });
+        });
+}
+
+fn handle_log_action(
+    query_action: InteractionQuery<&LogAction>,
+    mut query_body: Query<&mut Style, With<TransactionLogBody>>,
+) {
+    for (interaction, action) in &query_action {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                LogAction::Toggle => {
+                    for mut style in &mut query_body {
+                        style.display = match style.display {
+                            Display::None => Display::Flex,
+                            _ => Display::None,
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn describe(transaction: &Transaction, now: Duration) -> String {
+    let mut value = format!(
+        "{:>4}s ago  {}",
+        now.saturating_sub(transaction.time).as_secs(),
+        transaction.upgrade
+    );
+    if let Some(ring) = transaction.ring {
+        value += &format!(" (Ring {})", ring + 1);
+    }
+    match (transaction.failure, transaction.cost) {
+        (None, Some(cost)) if transaction.levels > 1 => {
+            value += &format!(" x{}  -{}", transaction.levels, cost)
+        }
+        (None, Some(cost)) => value += &format!("  -{}", cost),
+        (None, None) => {}
+        (Some(failure), _) => value += &format!("  failed: {}", failure.description()),
+    }
+    value
+}
+
+fn transaction_log_visible(query_body: Query<&Style, With<TransactionLogBody>>) -> bool {
+    query_body
+        .iter()
+        .any(|style| style.display != Display::None)
+}
+
+/// Redraws the entries when the log changes or is opened, and once a second for their ages.
+fn update_transaction_log_text(
+    clock: Res<ReplayClock>,
+    log: Res<TransactionLog>,
+    query_opened: Query<(), (With<TransactionLogBody>, Changed<Style>)>,
+    mut query_text: Query<&mut Text, With<TransactionLogText>>,
+    mut last_second: Local<Option<u64>>,
+) {
+    let now = clock.elapsed();
+    let second = now.as_secs();
+    if !log.is_changed() && query_opened.is_empty() && *last_second == Some(second) {
+        return;
+    }
+    *last_second = Some(second);
+    for mut text in &mut query_text {
+        text.sections = log
+            .iter()
+            .rev()
+            .take(SHOWN_TRANSACTIONS)
+            .enumerate()
+            .map(|(i, transaction)| {
+                let separator = if i == 0 { "" } else { "\n" };
+                TextSection::new(
+                    format!("{}{}", separator, describe(transaction, now)),
+                    TextStyle {
+                        font_size: 16.,
+                        color: if transaction.failure.is_some() {
+                            BUTTON_DISABLED_TEXT
+                        } else {
+                            BUTTON_TEXT
+                        },
+                        ..default()
+                    },
+                )
+            })
+            .collect();
+        if text.sections.is_empty() {
+            text.sections.push(TextSection::new(
+                "No purchases yet",
+                TextStyle {
+                    font_size: 16.,
+                    color: BUTTON_DISABLED_TEXT,
+                    ..default()
+                },
+            ));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Shows the current tutorial step and points at its target
+
+use bevy::prelude::*;
+
+use crate::game::spawn::atom::Electron;
+use crate::game::tutorial::{FinishTutorial, StepTarget, Tutorial};
+use crate::game::upgrades::UpgradeButton;
+use crate::input::InputBindings;
+use crate::screen::{InRun, Screen};
+use crate::ui::palette::{BUTTON_TEXT, HEADER_TEXT, LABEL_TEXT, NODE_BACKGROUND};
+use crate::ui::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(spawn_tutorial_ui);
+    app.add_systems(
+        Update,
+        (
+            handle_tutorial_action,
+            update_tutorial_panel,
+            highlight_tutorial_target,
+            draw_tutorial_pointer,
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnTutorialUi;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum TutorialAction {
+    Skip,
+}
+
+#[derive(Component)]
+struct TutorialPanel;
+
+#[derive(Component)]
+struct TutorialText;
+
+/// Marks the UI node currently outlined by the tutorial.
+#[derive(Component)]
+struct TutorialHighlight;
+
+const HIGHLIGHT_WIDTH: f32 = 3.;
+/// Radius of the circle drawn around world targets.
+const WORLD_HIGHLIGHT_RADIUS: f32 = 24.;
+
+fn spawn_tutorial_ui(_trigger: Trigger<SpawnTutorialUi>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Tutorial"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    bottom: Val::Px(60.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(InRun),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            display: Display::None,
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            width: Val::Px(420.),
+                            padding: UiRect::all(Val::Px(8.)),
+                            row_gap: Val::Px(6.),
+                            border: UiRect::all(Val::Px(2.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(NODE_BACKGROUND),
+                        border_color: BorderColor(HEADER_TEXT),
+                        ..default()
+                    },
+                    TutorialPanel,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_sections([
+                            TextSection::new(
+                                "",
+                                TextStyle {
+                                    font_size: 18.,
+                                    color: LABEL_TEXT,
+                                    ..default()
+                                },
+                            ),
+                            TextSection::new(
+                                "",
+                                TextStyle {
+                                    font_size: 22.,
+                                    color: BUTTON_TEXT,
+                                    ..default()
+                                },
+                            ),
+                        ])
+                        .with_text_justify(JustifyText::Center),
+                        TutorialText,
+                        Label,
+                    ));
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                                    ..default()
+                                },
+                                background_color: BackgroundColor(NODE_BACKGROUND),
+                                ..default()
+                            },
+                            InteractionPalette {
+                                none: NODE_BACKGROUND,
+                                hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+                                pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+                            },
+                            TutorialAction::Skip,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Skip tutorial",
+                                TextStyle {
+                                    font_size: 18.,
+                                    color: BUTTON_TEXT,
+                                    ..default()
+                                },
+                            ));
+                        });
+                });
+        });
+}
+
+fn handle_tutorial_action(mut commands: Commands, query_action: InteractionQuery<&TutorialAction>) {
+    for (interaction, action) in &query_action {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                TutorialAction::Skip => commands.trigger(FinishTutorial),
+            }
+        }
+    }
+}
+
+fn update_tutorial_panel(
+    tutorial: Res<Tutorial>,
+    bindings: Res<InputBindings>,
+    mut query_panel: Query<&mut Style, With<TutorialPanel>>,
+    mut query_text: Query<&mut Text, With<TutorialText>>,
+) {
+    if !tutorial.is_changed() && !bindings.is_changed() {
+        return;
+    }
+    let step = tutorial.current_step().zip(tutorial.progress());
+    for mut style in &mut query_panel {
+        style.display = if step.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    let Some((step, (number, count))) = step else {
+        return;
+    };
+    for mut text in &mut query_text {
+        text.sections[0].value = format!("Tutorial {}/{}\n", number, count);
+        text.sections[1].value = step.describe(&bindings);
+    }
+}
+
+/// The UI node the current step points at, if it points at one.
+fn target_node(
+    tutorial: &Tutorial,
+    query_button: &Query<(Entity, &UpgradeButton)>,
+) -> Option<Entity> {
+    let StepTarget::UpgradeButton(upgrade) = tutorial.current_step()?.target else {
+        return None;
+    };
+    query_button
+        .iter()
+        .find(|(_, button)| button.upgrade == upgrade)
+        .map(|(entity, _)| entity)
+}
+
+fn highlight_tutorial_target(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    query_button: Query<(Entity, &UpgradeButton)>,
+    query_highlighted: Query<Entity, With<TutorialHighlight>>,
+) {
+    let target = target_node(&tutorial, &query_button);
+    for entity in &query_highlighted {
+        if Some(entity) != target {
+            commands
+                .entity(entity)
+                .remove::<(TutorialHighlight, Outline)>();
+        }
+    }
+    if let Some(target) = target.filter(|target| !query_highlighted.contains(*target)) {
+        commands.entity(target).insert((
+            TutorialHighlight,
+            Outline::new(Val::Px(HIGHLIGHT_WIDTH), Val::ZERO, HEADER_TEXT),
+        ));
+    }
+}
+
+/// Draws an arrow from the tutorial panel to the current step's target.
+/// Gizmos are drawn beneath the UI, so the arrow ends at the edge of UI targets.
+fn draw_tutorial_pointer(
+    tutorial: Res<Tutorial>,
+    query_button: Query<(Entity, &UpgradeButton)>,
+    query_node: Query<(&Node, &GlobalTransform)>,
+    query_panel: Query<Entity, With<TutorialPanel>>,
+    query_electron: Query<&GlobalTransform, With<Electron>>,
+    query_camera: Query<(&Camera, &GlobalTransform), With<IsDefaultUiCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(step) = tutorial.current_step() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = query_camera.get_single() else {
+        return;
+    };
+    // UI nodes are positioned by their centre in viewport coordinates. Their corners are
+    // converted too, since the camera's scale changes as it zooms.
+    let node_to_world = |entity: Entity| {
+        let (node, transform) = query_node.get(entity).ok()?;
+        let center = transform.translation().truncate();
+        let corner = center + node.size() / 2.;
+        let center = camera.viewport_to_world_2d(camera_transform, center)?;
+        let corner = camera.viewport_to_world_2d(camera_transform, corner)?;
+        Some((center, (corner - center).abs()))
+    };
+    let Some((start, start_half_size)) = query_panel.get_single().ok().and_then(node_to_world)
+    else {
+        return;
+    };
+    if start_half_size == Vec2::ZERO {
+        return;
+    }
+
+    let (end, clearance) = match step.target {
+        StepTarget::UpgradeButton(_) => {
+            let Some((end, half_size)) =
+                target_node(&tutorial, &query_button).and_then(node_to_world)
+            else {
+                return;
+            };
+            (end, half_size.min_element())
+        }
+        StepTarget::Electron => {
+            let Some(transform) = query_electron.iter().next() else {
+                return;
+            };
+            let end = transform.translation().truncate();
+            gizmos.circle_2d(end, WORLD_HIGHLIGHT_RADIUS, HEADER_TEXT);
+            (end, WORLD_HIGHLIGHT_RADIUS)
+        }
+    };
+
+    let direction = (end - start).normalize_or_zero();
+    let start = start + direction * start_half_size.y;
+    let end = end - direction * clearance;
+    if start.distance(end) > clearance {
+        gizmos.arrow_2d(start, end, HEADER_TEXT);
+    }
+}
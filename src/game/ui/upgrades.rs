@@ -1,12 +1,17 @@
-use crate::game::movement::Revolve;
-use crate::game::spawn::atom::{Electron, Ring};
-use crate::game::upgrades::costs::{
-    compute_cycle_cost, compute_electron_cost, compute_ring_cost, compute_speed_cost,
+use crate::game::cycles::CycleCount;
+use crate::game::spawn::atom::Ring;
+use crate::game::stats::{CycleStats, STAT_WINDOWS};
+use crate::game::ui::ring_inspector::InspectRing;
+use crate::game::upgrades::costs::BulkCost;
+use crate::game::upgrades::{
+    quote_upgrade, resolve_target, PurchaseMultiplier, Upgrade, UpgradeButton, UpgradeTarget,
+    Upgrades,
+};
+use crate::screen::InRun;
+use crate::ui::{
+    interaction::{InteractionDisabled, InteractionPalette, InteractionQuery},
+    palette::*,
 };
-use crate::game::upgrades::BuyNextRing;
-use crate::game::upgrades::{BuyElectron, CycleUpgrade, SpeedUpgrade, INITIAL_REVOLVE_SPEED};
-use crate::screen::Screen;
-use crate::ui::{interaction::InteractionPalette, palette::*};
 use bevy::{
     a11y::{
         accesskit::{NodeBuilder, Role},
@@ -15,16 +20,19 @@ use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
+use bevy_mod_picking::{focus::HoverMap, pointer::PointerId};
 
 pub(super) fn plugin(app: &mut App) {
     app.observe(spawn_upgrades_ui).add_systems(
         Update,
         (
             add_new_upgrades,
-            update_buy_next_ring,
-            update_cycle_upgrades,
-            update_electron_upgrades,
-            update_speed_upgrades,
+            handle_multiplier_buttons,
+            update_multiplier_buttons,
+            update_upgrade_buttons,
+            update_upgrade_cost_text,
+            update_upgrade_affordability,
+            update_ring_rates,
             mouse_scroll,
         )
             .chain(),
@@ -37,6 +45,9 @@ pub(super) fn plugin(app: &mut App) {
     }
 }
 
+/// The width of the upgrades panel along the left of the screen, in pixels.
+pub const UPGRADES_PANEL_WIDTH: f32 = 400.;
+
 #[derive(Event, Debug)]
 pub struct SpawnUpgradesUi;
 
@@ -46,14 +57,87 @@ pub struct UpgradeList;
 #[derive(Component)]
 struct UpgradeText;
 
+/// Shows the cycles-per-second earned by a ring in its header.
+#[derive(Component)]
+struct RingRateText(Entity);
+
+/// What pressing an upgrade button will cost.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum UpgradeCost {
+    /// The summed cost of buying `levels` levels at once.
+    Cost { levels: u32, cost: u32 },
+    /// The upgrade can't be bought any more, the label is shown in place of the cost.
+    Maxed(&'static str),
+}
+
+/// Whether an upgrade button can currently be bought, derived from its [`UpgradeCost`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Affordability {
+    #[default]
+    Affordable,
+    Unaffordable,
+    Maxed,
+}
+
+impl Affordability {
+    fn palette(&self) -> InteractionPalette {
+        match self {
+            Affordability::Affordable => InteractionPalette {
+                none: NODE_BACKGROUND,
+                hovered: BUTTON_HOVERED_BACKGROUND,
+                pressed: BUTTON_PRESSED_BACKGROUND,
+            },
+            Affordability::Unaffordable => InteractionPalette {
+                none: BUTTON_DISABLED_BACKGROUND,
+                hovered: BUTTON_DISABLED_BACKGROUND,
+                pressed: BUTTON_DISABLED_BACKGROUND,
+            },
+            Affordability::Maxed => InteractionPalette {
+                none: BUTTON_MAXED_BACKGROUND,
+                hovered: BUTTON_MAXED_BACKGROUND,
+                pressed: BUTTON_MAXED_BACKGROUND,
+            },
+        }
+    }
+
+    fn text_color(&self) -> Color {
+        match self {
+            Affordability::Affordable | Affordability::Maxed => BUTTON_TEXT,
+            Affordability::Unaffordable => BUTTON_DISABLED_TEXT,
+        }
+    }
+}
+
+/// Selects the [`PurchaseMultiplier`] used by every upgrade button.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+struct MultiplierButton(PurchaseMultiplier);
+
+fn multiplier_palette(selected: bool) -> InteractionPalette {
+    InteractionPalette {
+        none: if selected {
+            BUTTON_PRESSED_BACKGROUND
+        } else {
+            NODE_BACKGROUND
+        },
+        hovered: BUTTON_HOVERED_BACKGROUND,
+        pressed: BUTTON_PRESSED_BACKGROUND,
+    }
+}
+
+/// A bar along the bottom of an upgrade button showing progress towards affording it.
+#[derive(Component)]
+struct AffordFill;
+
 #[derive(Bundle)]
 struct UpgradeButtonBundle {
     button_bundle: ButtonBundle,
     interaction_palette: InteractionPalette,
+    cost: UpgradeCost,
+    affordability: Affordability,
 }
 
 impl UpgradeButtonBundle {
-    pub fn new(width: f32) -> Self {
+    pub fn new(width: f32, cost: UpgradeCost) -> Self {
         Self {
             button_bundle: ButtonBundle {
                 style: Style {
@@ -72,39 +156,113 @@ impl UpgradeButtonBundle {
                 background_color: BackgroundColor(NODE_BACKGROUND),
                 ..default()
             },
-            interaction_palette: InteractionPalette {
-                none: NODE_BACKGROUND,
-                hovered: BUTTON_HOVERED_BACKGROUND,
-                pressed: BUTTON_PRESSED_BACKGROUND,
-            },
+            interaction_palette: Affordability::Affordable.palette(),
+            cost,
+            affordability: Affordability::Affordable,
         }
     }
 }
 
-#[derive(Bundle)]
-struct UpgradeTextBundle {
-    text_bundle: TextBundle,
-    upgrade_text: UpgradeText,
-    label: Label,
-    accessibility_node: AccessibilityNode,
+fn spawn_afford_fill(parent: &mut ChildBuilder) {
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                bottom: Val::Px(0.),
+                width: Val::Percent(0.),
+                height: Val::Px(3.),
+                ..default()
+            },
+            background_color: BackgroundColor(LABEL_TEXT),
+            ..default()
+        },
+        AffordFill,
+    ));
+}
+
+/// The text entities of an upgrade button, so they can be updated without relying on the
+/// order of its children.
+#[derive(Component, Debug, Clone, Copy)]
+struct UpgradeTexts {
+    name: Entity,
+    value: Entity,
+    cost: Entity,
 }
 
-fn spawn_upgrades_ui(_trigger: Trigger<SpawnUpgradesUi>, mut commands: Commands) {
+impl UpgradeTexts {
+    fn all(&self) -> [Entity; 3] {
+        [self.name, self.value, self.cost]
+    }
+}
+
+fn upgrade_text(value: impl Into<String>, font_size: f32) -> impl Bundle {
+    (
+        TextBundle::from_section(
+            value,
+            TextStyle {
+                font_size,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center),
+        UpgradeText,
+        Label,
+    )
+}
+
+/// Spawns a button buying `upgrade`, its text is filled in by [`update_upgrade_buttons`].
+fn spawn_upgrade_button(
+    parent: &mut ChildBuilder,
+    upgrade: &dyn Upgrade,
+    ring: Option<Entity>,
+    width: f32,
+) {
+    let mut button = parent.spawn((
+        Name::new(upgrade.name()),
+        UpgradeButtonBundle::new(width, UpgradeCost::Cost { levels: 1, cost: 0 }),
+        UpgradeButton {
+            upgrade: upgrade.id(),
+            ring,
+        },
+        AccessibilityNode(NodeBuilder::new(Role::ListItem)),
+    ));
+
+    let mut texts = None;
+    button.with_children(|parent| {
+        texts = Some(UpgradeTexts {
+            name: parent.spawn(upgrade_text(upgrade.name(), 18.)).id(),
+            value: parent.spawn(upgrade_text("", 24.)).id(),
+            cost: parent.spawn(upgrade_text("", 18.)).id(),
+        });
+        spawn_afford_fill(parent);
+    });
+    if let Some(texts) = texts {
+        button.insert(texts);
+    }
+}
+
+fn spawn_upgrades_ui(
+    _trigger: Trigger<SpawnUpgradesUi>,
+    mut commands: Commands,
+    upgrades: Res<Upgrades>,
+) {
     // root node
     commands
         .spawn((
+            Name::new("Upgrades Panel"),
             NodeBundle {
                 style: Style {
                     flex_direction: FlexDirection::Column,
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
-                    width: Val::Px(400.),
+                    width: Val::Px(UPGRADES_PANEL_WIDTH),
                     height: Val::Percent(95.),
                     ..default()
                 },
                 ..default()
             },
-            StateScoped(Screen::Playing),
+            StateScoped(InRun),
         ))
         .with_children(|parent| {
             // Title
@@ -112,6 +270,46 @@ fn spawn_upgrades_ui(_trigger: Trigger<SpawnUpgradesUi>, mut commands: Commands)
                 TextBundle::from_section("Upgrades", TextStyle::default()),
                 Label,
             ));
+            // Purchase multiplier selector
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(5.),
+                        margin: UiRect::top(Val::Px(5.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for multiplier in PurchaseMultiplier::ALL {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(NODE_BACKGROUND),
+                                    ..default()
+                                },
+                                multiplier_palette(false),
+                                MultiplierButton(multiplier),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        multiplier.label(),
+                                        TextStyle {
+                                            font_size: 18.,
+                                            ..default()
+                                        },
+                                    ),
+                                    Label,
+                                ));
+                            });
+                    }
+                });
             // List with hidden overflow
             parent
                 .spawn(NodeBundle {
@@ -147,7 +345,7 @@ fn spawn_upgrades_ui(_trigger: Trigger<SpawnUpgradesUi>, mut commands: Commands)
                             AccessibilityNode(NodeBuilder::new(Role::List)),
                         ))
                         .with_children(|parent| {
-                            // List items
+                            // Atom upgrades
                             parent
                                 .spawn((NodeBundle {
                                     style: Style {
@@ -159,40 +357,9 @@ fn spawn_upgrades_ui(_trigger: Trigger<SpawnUpgradesUi>, mut commands: Commands)
                                     ..default()
                                 },))
                                 .with_children(|parent| {
-                                    let cost = compute_ring_cost(0);
-                                    parent
-                                        .spawn((UpgradeButtonBundle::new(100.), BuyNextRing))
-                                        .with_children(|parent| {
-                                            parent.spawn((
-                                                TextBundle::from_sections([
-                                                    TextSection::new(
-                                                        "Buy Ring",
-                                                        TextStyle {
-                                                            font_size: 18.,
-                                                            ..default()
-                                                        },
-                                                    ),
-                                                    TextSection::new(
-                                                        "\nCost: ",
-                                                        TextStyle {
-                                                            font_size: 18.,
-                                                            ..default()
-                                                        },
-                                                    ),
-                                                    TextSection::new(
-                                                        cost.to_string(),
-                                                        TextStyle {
-                                                            font_size: 18.,
-                                                            ..default()
-                                                        },
-                                                    ),
-                                                ])
-                                                .with_text_justify(JustifyText::Center),
-                                                UpgradeText,
-                                                Label,
-                                                AccessibilityNode(NodeBuilder::new(Role::ListItem)),
-                                            ));
-                                        });
+                                    for upgrade in upgrades.for_target(UpgradeTarget::Atom) {
+                                        spawn_upgrade_button(parent, upgrade.as_ref(), None, 100.);
+                                    }
                                 });
                         });
                 });
@@ -201,12 +368,17 @@ fn spawn_upgrades_ui(_trigger: Trigger<SpawnUpgradesUi>, mut commands: Commands)
 
 fn add_new_upgrades(
     mut commands: Commands,
+    upgrades: Res<Upgrades>,
     query_list: Query<Entity, With<UpgradeList>>,
     query_ring: Query<(Entity, &Ring), Added<Ring>>,
 ) {
     let Ok(parent) = query_list.get_single() else {
         return;
     };
+    let ring_upgrades: Vec<_> = upgrades.for_target(UpgradeTarget::Ring).collect();
+    // Leave room for the separator after the buttons.
+    let width = 98. / ring_upgrades.len().max(1) as f32;
+
     for (entity, ring) in &query_ring {
         commands.entity(parent).with_children(|parent| {
             let title = format!("Ring {}", ring.index + 1);
@@ -224,15 +396,40 @@ fn add_new_upgrades(
                     },
                 ))
                 .with_children(|parent| {
-                    // Header
-                    parent.spawn(TextBundle {
-                        text: Text::from_section(title, TextStyle::default()),
-                        style: Style {
-                            width: Val::Percent(100.),
-                            ..default()
-                        },
-                        ..Default::default()
-                    });
+                    // Header, pressing it opens the ring inspector
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Percent(100.),
+                                    ..default()
+                                },
+                                background_color: BackgroundColor(Color::NONE),
+                                ..default()
+                            },
+                            InteractionPalette {
+                                none: Color::NONE,
+                                hovered: BUTTON_HOVERED_BACKGROUND,
+                                pressed: BUTTON_PRESSED_BACKGROUND,
+                            },
+                            InspectRing(entity),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_sections([
+                                    TextSection::new(title, TextStyle::default()),
+                                    TextSection::new(
+                                        "",
+                                        TextStyle {
+                                            font_size: 18.,
+                                            color: LABEL_TEXT,
+                                            ..default()
+                                        },
+                                    ),
+                                ]),
+                                RingRateText(entity),
+                            ));
+                        });
                     parent
                         .spawn(NodeBundle {
                             style: Style {
@@ -244,125 +441,9 @@ fn add_new_upgrades(
                             ..default()
                         })
                         .with_children(|parent| {
-                            // Buy electron
-                            let electron_cost = compute_electron_cost(ring.index, 0);
-                            parent
-                                .spawn((UpgradeButtonBundle::new(38.), BuyElectron(entity)))
-                                .with_children(|parent| {
-                                    parent.spawn((
-                                        TextBundle::from_sections([
-                                            TextSection::new(
-                                                "Electrons\n",
-                                                TextStyle {
-                                                    font_size: 18.,
-
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new("0", TextStyle::default()),
-                                            TextSection::new(
-                                                "\nCost: ",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(
-                                                electron_cost.to_string(),
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                        ])
-                                        .with_text_justify(JustifyText::Center),
-                                        UpgradeText,
-                                        Label,
-                                        AccessibilityNode(NodeBuilder::new(Role::ListItem)),
-                                    ));
-                                });
-                            // SPEED upgrade
-                            let speed_cost = compute_speed_cost(ring.index, 0);
-                            parent
-                                .spawn((UpgradeButtonBundle::new(30.), SpeedUpgrade(entity)))
-                                .with_children(|parent| {
-                                    parent.spawn((
-                                        TextBundle::from_sections([
-                                            TextSection::new(
-                                                "Speed\n",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(
-                                                format!("{:.2}", INITIAL_REVOLVE_SPEED),
-                                                TextStyle::default(),
-                                            ),
-                                            TextSection::new(
-                                                "\nCost: ",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(
-                                                format!("{:.2}", speed_cost),
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                        ])
-                                        .with_text_justify(JustifyText::Center),
-                                        UpgradeText,
-                                        Label,
-                                        AccessibilityNode(NodeBuilder::new(Role::ListItem)),
-                                    ));
-                                });
-                            // CYCLE
-                            let cycle_cost = compute_cycle_cost(ring.index, None);
-                            parent
-                                .spawn((UpgradeButtonBundle::new(30.), CycleUpgrade(entity)))
-                                .with_children(|parent| {
-                                    parent.spawn((
-                                        TextBundle::from_sections([
-                                            TextSection::new(
-                                                "Cycles\n",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(" ".to_string(), TextStyle::default()),
-                                            TextSection::new(
-                                                "s",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(
-                                                "\nCost: ",
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                            TextSection::new(
-                                                cycle_cost.to_string(),
-                                                TextStyle {
-                                                    font_size: 18.,
-                                                    ..default()
-                                                },
-                                            ),
-                                        ])
-                                        .with_text_justify(JustifyText::Center),
-                                        UpgradeText,
-                                        Label,
-                                        AccessibilityNode(NodeBuilder::new(Role::ListItem)),
-                                    ));
-                                });
+                            for upgrade in &ring_upgrades {
+                                spawn_upgrade_button(parent, upgrade.as_ref(), Some(entity), width);
+                            }
                             parent.spawn(NodeBundle {
                                 style: Style {
                                     flex_direction: FlexDirection::Column,
@@ -380,129 +461,176 @@ fn add_new_upgrades(
     }
 }
 
-fn update_buy_next_ring(
-    query_ring: Query<&Ring, Added<Ring>>,
-    query_upgrade: Query<&Children, With<BuyNextRing>>,
-    mut query_upgrade_text: Query<&mut Text, With<UpgradeText>>,
+/// Prices every upgrade button for the selected [`PurchaseMultiplier`] and shows the
+/// upgrade's current value.
+/// Runs every frame as [`PurchaseMultiplier::Max`] depends on the current [`CycleCount`].
+fn update_upgrade_buttons(
+    world: &mut World,
+    query_button: &mut QueryState<(Entity, &UpgradeButton, &UpgradeTexts)>,
 ) {
-    for ring in &query_ring {
-        let cost = compute_ring_cost(ring.index + 1);
+    let upgrades = world.resource::<Upgrades>().clone();
+    let multiplier = *world.resource::<PurchaseMultiplier>();
+    let budget = world.resource::<CycleCount>().0;
+
+    let updates: Vec<(Entity, Entity, UpgradeCost, String)> = query_button
+        .iter(world)
+        .filter_map(|(entity, button, texts)| {
+            let upgrade = upgrades.get(button.upgrade)?;
+            let target = resolve_target(world, upgrade.as_ref(), button.ring)?;
+            let cost = match quote_upgrade(world, upgrade.as_ref(), target, multiplier, budget) {
+                Some(BulkCost { levels, cost }) => UpgradeCost::Cost { levels, cost },
+                None => UpgradeCost::Maxed(upgrade.maxed_text()),
+            };
+            Some((entity, texts.value, cost, upgrade.value_text(world, target)))
+        })
+        .collect();
 
-        let Ok(upgrade_entity) = query_upgrade.get_single() else {
-            continue;
-        };
+    for (entity, value_text, cost, value) in updates {
+        if let Some(mut upgrade_cost) = world.get_mut::<UpgradeCost>(entity) {
+            upgrade_cost.set_if_neq(cost);
+        }
+        if let Some(mut text) = world.get_mut::<Text>(value_text) {
+            if text.sections[0].value != value {
+                text.sections[0].value = value;
+            }
+        }
+    }
+}
 
-        let Ok(mut text) = query_upgrade_text.get_mut(upgrade_entity[0]) else {
-            continue;
-        };
+fn handle_multiplier_buttons(
+    mut multiplier: ResMut<PurchaseMultiplier>,
+    query_button: InteractionQuery<&MultiplierButton>,
+) {
+    for (interaction, button) in &query_button {
+        if matches!(interaction, Interaction::Pressed) {
+            *multiplier = button.0;
+        }
+    }
+}
 
-        text.sections[2].value = format!("{}", cost);
+fn update_multiplier_buttons(
+    multiplier: Res<PurchaseMultiplier>,
+    mut query_button: Query<(
+        &MultiplierButton,
+        &Interaction,
+        &mut InteractionPalette,
+        &mut BackgroundColor,
+    )>,
+    query_added: Query<(), Added<MultiplierButton>>,
+) {
+    if !multiplier.is_changed() && query_added.is_empty() {
+        return;
+    }
+    for (button, interaction, mut palette, mut background) in &mut query_button {
+        *palette = multiplier_palette(button.0 == *multiplier);
+        if matches!(interaction, Interaction::None) {
+            *background = palette.none.into();
+        }
     }
 }
 
-fn update_speed_upgrades(
-    query_ring: Query<(Entity, &Ring, &Revolve), Changed<Revolve>>,
-    query_upgrade: Query<(&SpeedUpgrade, &Children)>,
+/// Writes the cost, or the maxed label, into an upgrade's cost text.
+fn update_upgrade_cost_text(
+    query_upgrade: Query<(&UpgradeCost, &UpgradeTexts), Changed<UpgradeCost>>,
     mut query_upgrade_text: Query<&mut Text, With<UpgradeText>>,
 ) {
-    for (entity, ring, revolve) in &query_ring {
-        let cost = compute_speed_cost(ring.index, revolve.level);
-
-        let Some(upgrade_entity) = query_upgrade
-            .iter()
-            .find(|(upgrade, _)| upgrade.0 == entity)
-            .map(|(_, children)| children[0])
-        else {
+    for (upgrade_cost, texts) in &query_upgrade {
+        let Ok(mut text) = query_upgrade_text.get_mut(texts.cost) else {
             continue;
         };
-
-        let Ok(mut text) = query_upgrade_text.get_mut(upgrade_entity) else {
-            continue;
+        text.sections[0].value = match upgrade_cost {
+            UpgradeCost::Cost { levels: 1, cost } => format!("Cost: {}", cost),
+            UpgradeCost::Cost { levels, cost } => format!("Cost (x{}): {}", levels, cost),
+            UpgradeCost::Maxed(label) => label.to_string(),
         };
-
-        text.sections[1].value = format!("{:.2}", revolve.speed());
-        text.sections[3].value = format!("{}", cost);
-        log::info!("Speed: {}, Cost: {}", revolve.speed(), cost);
     }
 }
 
-fn update_cycle_upgrades(
-    query_ring: Query<(Entity, &Ring), Changed<Ring>>,
-    query_upgrade: Query<(&CycleUpgrade, &Children)>,
+fn update_upgrade_affordability(
+    mut commands: Commands,
+    cycle_count: Res<CycleCount>,
+    mut query_upgrade: Query<(
+        Entity,
+        &UpgradeCost,
+        &Interaction,
+        &Children,
+        &UpgradeTexts,
+        &mut Affordability,
+        &mut InteractionPalette,
+        &mut BackgroundColor,
+    )>,
     mut query_upgrade_text: Query<&mut Text, With<UpgradeText>>,
+    mut query_fill: Query<&mut Style, With<AffordFill>>,
 ) {
-    for (entity, ring) in &query_ring {
-        let duration = ring.cycle_timer.as_ref().map(|t| t.duration());
-        let cost = compute_cycle_cost(ring.index, duration);
-
-        let Some(upgrade_entity) = query_upgrade
-            .iter()
-            .find(|(upgrade, _)| upgrade.0 == entity)
-            .map(|(_, children)| children[0])
-        else {
-            continue;
+    for (
+        entity,
+        upgrade_cost,
+        interaction,
+        children,
+        texts,
+        mut affordability,
+        mut palette,
+        mut background,
+    ) in &mut query_upgrade
+    {
+        let (state, progress) = match *upgrade_cost {
+            UpgradeCost::Maxed(_) => (Affordability::Maxed, 0.),
+            UpgradeCost::Cost { cost, .. } if cost <= cycle_count.0 => {
+                (Affordability::Affordable, 0.)
+            }
+            UpgradeCost::Cost { cost, .. } => (
+                Affordability::Unaffordable,
+                cycle_count.0 as f32 / cost as f32,
+            ),
         };
 
-        let Ok(mut text) = query_upgrade_text.get_mut(upgrade_entity) else {
+        for child in children {
+            if let Ok(mut style) = query_fill.get_mut(*child) {
+                let width = Val::Percent(progress * 100.);
+                if style.width != width {
+                    style.width = width;
+                }
+            }
+        }
+
+        if *affordability == state {
             continue;
-        };
+        }
+        *affordability = state;
+        *palette = state.palette();
+        *background = match interaction {
+            Interaction::None => palette.none,
+            Interaction::Hovered => palette.hovered,
+            Interaction::Pressed => palette.pressed,
+        }
+        .into();
 
-        text.sections[1].value = match duration {
-            Some(duration) => {
-                format!("{:.2}", duration.as_secs_f32())
+        for text in texts.all() {
+            if let Ok(mut text) = query_upgrade_text.get_mut(text) {
+                for section in &mut text.sections {
+                    section.style.color = state.text_color();
+                }
             }
-            None => " ".to_string(),
-        };
+        }
 
-        text.sections[4].value = format!("{}", cost);
+        if state == Affordability::Affordable {
+            commands.entity(entity).remove::<InteractionDisabled>();
+        } else {
+            commands.entity(entity).insert(InteractionDisabled);
+        }
     }
 }
 
-fn update_electron_upgrades(
-    query_added_electron: Query<&Parent, Added<Electron>>,
-    query_electrons: Query<Entity, With<Electron>>,
-    query_ring: Query<(&Ring, Option<&Children>)>,
-    query_upgrade: Query<(&BuyElectron, &Children)>,
-    mut query_upgrade_text: Query<&mut Text, With<UpgradeText>>,
+fn update_ring_rates(
+    stats: Res<CycleStats>,
+    query_ring: Query<&Ring>,
+    mut query_text: Query<(&mut Text, &RingRateText)>,
 ) {
-    for added_electron_parent in &query_added_electron {
-        let Ok((ring, children)) = query_ring.get(added_electron_parent.get()) else {
-            continue;
-        };
-
-        let electron_count = {
-            children
-                .map(|children| {
-                    children
-                        .iter()
-                        .filter_map(|child| query_electrons.get(*child).ok())
-                        .count()
-                })
-                .unwrap_or_default()
-        };
-
-        let cost = compute_electron_cost(ring.index, electron_count);
-
-        let Some(upgrade_entity) = query_upgrade
-            .iter()
-            .find(|(upgrade, _)| upgrade.0 == added_electron_parent.get())
-            .map(|(_, children)| children[0])
-        else {
+    for (mut text, rate_text) in &mut query_text {
+        let Ok(ring) = query_ring.get(rate_text.0) else {
             continue;
         };
-
-        let Ok(mut text) = query_upgrade_text.get_mut(upgrade_entity) else {
-            continue;
-        };
-
-        if ring.max_electrons == electron_count {
-            text.sections[1].value = "Full".to_string();
-            text.sections[2].value = " ".to_string();
-            text.sections[3].value = " ".to_string();
-        } else {
-            text.sections[1].value = format!("{}", electron_count);
-            text.sections[3].value = format!("{}", cost);
-        }
+        text.sections[1].value = format!("  {:.2}/s", stats.ring_rate(STAT_WINDOWS[1], ring.index));
     }
 }
 
@@ -511,15 +639,29 @@ struct ScrollingList {
     position: f32,
 }
 
+/// Scrolls the lists the mouse is over. Elsewhere the mouse wheel zooms the camera.
 fn mouse_scroll(
     mut mouse_wheel_events: EventReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
     mut query_list: Query<(&mut ScrollingList, &mut Style, &Parent, &Node)>,
     query_node: Query<&Node>,
+    query_parent: Query<&Parent>,
 ) {
+    let hovered = hover_map.get(&PointerId::Mouse);
     for mouse_wheel_event in mouse_wheel_events.read() {
         for (mut scrolling_list, mut style, parent, list_node) in &mut query_list {
+            let container = parent.get();
+            let over_list = hovered.is_some_and(|hovered| {
+                hovered.keys().any(|entity| {
+                    *entity == container
+                        || query_parent.iter_ancestors(*entity).any(|e| e == container)
+                })
+            });
+            if !over_list {
+                continue;
+            }
             let items_height = list_node.size().y;
-            let container_height = query_node.get(parent.get()).unwrap().size().y;
+            let container_height = query_node.get(container).unwrap().size().y;
 
             let max_scroll = (items_height - container_height).max(0.);
 
@@ -538,11 +680,13 @@ fn mouse_scroll(
 #[cfg(feature = "dev")]
 // The system that will enable/disable the debug outlines around the nodes
 fn toggle_overlay(
-    input: Res<ButtonInput<KeyCode>>,
+    input: crate::input::ActionInput,
     mut options: ResMut<bevy::dev_tools::ui_debug_overlay::UiDebugOptions>,
 ) {
-    info_once!("The debug outlines are enabled, press Space to turn them on/off");
-    if input.just_pressed(KeyCode::Space) {
+    use crate::input::InputAction;
+
+    info_once!("The debug outlines are enabled, press F1 (or its new binding) to turn them on/off");
+    if input.just_pressed(InputAction::ToggleUiOverlay) {
         // The toggle method will enable the debug_overlay if disabled and disable if enabled
         options.toggle();
     }
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use super::PurchaseMultiplier;
+
 pub const STARTING_CYCLES: u32 = compute_ring_cost(0) + compute_electron_cost(0, 0);
 
 pub const COST_SCALE: u32 = 2;
@@ -24,10 +26,62 @@ pub fn compute_speed_cost(rings: usize, level: u32) -> u32 {
 }
 
 pub fn compute_cycle_cost(rings: usize, duration: Option<Duration>) -> u32 {
+    // Saturate, bulk purchases can project the timer down to almost nothing.
     (if let Some(duration) = duration {
         let secs = duration.as_secs_f32();
-        (3. / secs) as u32 + 5
+        ((3. / secs) as u32).saturating_add(5)
     } else {
         3
-    }) * (rings as u32 + 1).pow(2) / COST_SCALE
+    })
+    .saturating_mul((rings as u32 + 1).pow(2))
+        / COST_SCALE
+}
+
+/// The number of levels bought together and their summed cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkCost {
+    pub levels: u32,
+    pub cost: u32,
+}
+
+/// Sums the cost of buying several levels at once.
+/// `level_costs` yields the cost of each successive level and ends when no more can be bought.
+/// [`PurchaseMultiplier::Max`] buys as many levels as `budget` allows, or quotes a single level
+/// if not even one is affordable.
+/// Returns `None` if there are no levels left to buy.
+pub fn compute_bulk_cost(
+    level_costs: impl IntoIterator<Item = u32>,
+    multiplier: PurchaseMultiplier,
+    budget: u32,
+) -> Option<BulkCost> {
+    let mut level_costs = level_costs.into_iter().peekable();
+    let first = *level_costs.peek()?;
+
+    let mut bulk = BulkCost { levels: 0, cost: 0 };
+    match multiplier.levels() {
+        Some(levels) => {
+            for cost in level_costs.take(levels as usize) {
+                bulk.levels += 1;
+                bulk.cost = bulk.cost.saturating_add(cost);
+            }
+        }
+        None => {
+            for cost in level_costs {
+                let total = bulk.cost.saturating_add(cost);
+                // Costs that saturate would otherwise keep fitting a budget of `u32::MAX`.
+                if total > budget || total == u32::MAX {
+                    break;
+                }
+                bulk.levels += 1;
+                bulk.cost = total;
+            }
+            if bulk.levels == 0 {
+                bulk = BulkCost {
+                    levels: 1,
+                    cost: first,
+                };
+            }
+        }
+    }
+    Some(bulk)
 }
@@ -0,0 +1,284 @@
+//! The upgrades available in the game
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use bevy_mod_picking::prelude::*;
+
+use super::{costs, Upgrade, UpgradeId, UpgradeTarget};
+use crate::game::assets::{HandleMap, ImageKey};
+use crate::game::movement::Revolve;
+use crate::game::spawn::atom::{AddProton, AddProtonNeutron, Electron, ElectronBundle, Ring};
+
+pub const MAX_RINGS: usize = 5;
+pub const INITIAL_REVOLVE_SPEED: f32 = 3.0;
+pub const SPEED_UPGRADE_INCREMENT: f32 = 1.0;
+/// The number of vertices around a ring's mesh, enough for its orbit line to look round.
+const RING_RESOLUTION: usize = 128;
+pub const INITIAL_CYCLE_TIME: Duration = Duration::from_secs(3);
+
+/// Each cycle upgrade after the first shortens the timer to this share of its duration.
+const CYCLE_TIME_FACTOR: f64 = 0.8;
+
+/// The cycle timer duration after the next cycle upgrade.
+pub fn next_cycle_duration(current: Option<Duration>) -> Duration {
+    cycle_duration_after(current, 1).unwrap_or(INITIAL_CYCLE_TIME)
+}
+
+/// The cycle timer duration after `levels` more cycle upgrades, or `None` if the ring still has
+/// no timer. Computed in one step, so pricing many levels doesn't apply them one by one.
+pub fn cycle_duration_after(current: Option<Duration>, levels: u32) -> Option<Duration> {
+    let (duration, shortenings) = match current {
+        Some(duration) => (duration, levels),
+        // The first level starts the timer, the rest shorten it.
+        None => (INITIAL_CYCLE_TIME, levels.checked_sub(1)?),
+    };
+    let shortenings = shortenings.min(i32::MAX as u32) as i32;
+    Some(duration.mul_f64(CYCLE_TIME_FACTOR.powi(shortenings)))
+}
+
+/// The number of children of `entity` with a `T` component.
+fn count_children<T: Component>(world: &World, entity: Entity) -> usize {
+    world
+        .get::<Children>(entity)
+        .map(|children| {
+            children
+                .iter()
+                .filter(|child| world.get::<T>(**child).is_some())
+                .count()
+        })
+        .unwrap_or_default()
+}
+
+/// Adds the next ring to the atom.
+pub struct BuyNextRing;
+
+impl Upgrade for BuyNextRing {
+    fn id(&self) -> UpgradeId {
+        UpgradeId("buy_next_ring")
+    }
+
+    fn name(&self) -> &'static str {
+        "Buy Ring"
+    }
+
+    fn target(&self) -> UpgradeTarget {
+        UpgradeTarget::Atom
+    }
+
+    fn remaining(&self, world: &World, atom: Entity) -> Option<u32> {
+        Some(MAX_RINGS.saturating_sub(count_children::<Ring>(world, atom)) as u32)
+    }
+
+    fn cost(&self, world: &World, atom: Entity, offset: u32) -> Option<u32> {
+        Some(costs::compute_ring_cost(
+            count_children::<Ring>(world, atom) + offset as usize,
+        ))
+    }
+
+    fn apply(&self, world: &mut World, atom: Entity, levels: u32) {
+        let ring_count = count_children::<Ring>(world, atom);
+        for index in ring_count..ring_count + levels as usize {
+            let ring = Ring::new(index);
+            let mesh = world.resource_mut::<Assets<Mesh>>().add(
+                Circle::new(ring.radius())
+                    .mesh()
+                    .resolution(RING_RESOLUTION),
+            );
+            // Plain rings until the ring shader loads, see `ring_material`.
+            let material = world
+                .resource_mut::<Assets<ColorMaterial>>()
+                .add(Color::srgba_u8(0x28, 0x66, 0x6e, 0x66));
+            world.entity_mut(atom).with_children(|parent| {
+                parent.spawn((
+                    ring,
+                    MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(mesh),
+                        material,
+                        // Inner rings sit in front so they are picked before the rings around them.
+                        transform: Transform::from_xyz(0., 0., -100. - index as f32),
+                        ..default()
+                    },
+                    Revolve::new(INITIAL_REVOLVE_SPEED),
+                    PickableBundle::default(),
+                ));
+            });
+        }
+    }
+
+    fn maxed_text(&self) -> &'static str {
+        "All rings bought"
+    }
+}
+
+/// Adds an electron, and a proton to balance it, to a ring.
+pub struct BuyElectron;
+
+impl Upgrade for BuyElectron {
+    fn id(&self) -> UpgradeId {
+        UpgradeId("buy_electron")
+    }
+
+    fn name(&self) -> &'static str {
+        "Electrons"
+    }
+
+    fn target(&self) -> UpgradeTarget {
+        UpgradeTarget::Ring
+    }
+
+    fn remaining(&self, world: &World, ring: Entity) -> Option<u32> {
+        let max_electrons = world.get::<Ring>(ring)?.max_electrons;
+        Some(max_electrons.saturating_sub(count_children::<Electron>(world, ring)) as u32)
+    }
+
+    fn cost(&self, world: &World, ring: Entity, offset: u32) -> Option<u32> {
+        let index = world.get::<Ring>(ring)?.index;
+        Some(costs::compute_electron_cost(
+            index,
+            count_children::<Electron>(world, ring) + offset as usize,
+        ))
+    }
+
+    fn apply(&self, world: &mut World, entity: Entity, levels: u32) {
+        let Some(ring) = world.get::<Ring>(entity) else {
+            return;
+        };
+        let electron_count = count_children::<Electron>(world, entity);
+        let image_handles = world.resource::<HandleMap<ImageKey>>();
+        let electrons: Vec<(usize, ElectronBundle)> = (electron_count
+            ..electron_count + levels as usize)
+            .map(|index| {
+                let electron = ElectronBundle::new(ring.index, index, ring.radius(), image_handles);
+                (index, electron)
+            })
+            .collect();
+        let ring_index = ring.index;
+
+        for (index, electron) in electrons {
+            world.entity_mut(entity).with_children(|parent| {
+                parent.spawn(electron);
+            });
+
+            if ring_index == 0 && index == 0 {
+                world.trigger(AddProton);
+            } else {
+                world.trigger(AddProtonNeutron);
+            }
+        }
+    }
+
+    fn value_text(&self, world: &World, ring: Entity) -> String {
+        let max_electrons = world
+            .get::<Ring>(ring)
+            .map(|ring| ring.max_electrons)
+            .unwrap_or_default();
+        format!(
+            "{}/{}",
+            count_children::<Electron>(world, ring),
+            max_electrons
+        )
+    }
+
+    fn maxed_text(&self) -> &'static str {
+        "Full"
+    }
+}
+
+/// Makes a ring's electrons revolve faster.
+pub struct SpeedUpgrade;
+
+impl Upgrade for SpeedUpgrade {
+    fn id(&self) -> UpgradeId {
+        UpgradeId("speed")
+    }
+
+    fn name(&self) -> &'static str {
+        "Speed"
+    }
+
+    fn target(&self) -> UpgradeTarget {
+        UpgradeTarget::Ring
+    }
+
+    fn remaining(&self, _world: &World, _ring: Entity) -> Option<u32> {
+        None
+    }
+
+    fn cost(&self, world: &World, ring: Entity, offset: u32) -> Option<u32> {
+        let index = world.get::<Ring>(ring)?.index;
+        let level = world.get::<Revolve>(ring)?.level;
+        Some(costs::compute_speed_cost(index, level + offset))
+    }
+
+    fn apply(&self, world: &mut World, ring: Entity, levels: u32) {
+        if let Some(mut revolve) = world.get_mut::<Revolve>(ring) {
+            revolve.speed += SPEED_UPGRADE_INCREMENT * levels as f32;
+            revolve.level += levels;
+        }
+    }
+
+    fn value_text(&self, world: &World, ring: Entity) -> String {
+        world
+            .get::<Revolve>(ring)
+            .map(|revolve| format!("{:.2}", revolve.speed()))
+            .unwrap_or_default()
+    }
+}
+
+/// Gives a ring a cycle timer, then makes it shorter.
+pub struct CycleUpgrade;
+
+impl CycleUpgrade {
+    fn duration(world: &World, ring: Entity) -> Option<Duration> {
+        world
+            .get::<Ring>(ring)
+            .and_then(|ring| ring.cycle_timer.as_ref())
+            .map(Timer::duration)
+    }
+}
+
+impl Upgrade for CycleUpgrade {
+    fn id(&self) -> UpgradeId {
+        UpgradeId("cycle")
+    }
+
+    fn name(&self) -> &'static str {
+        "Cycles"
+    }
+
+    fn target(&self) -> UpgradeTarget {
+        UpgradeTarget::Ring
+    }
+
+    fn remaining(&self, _world: &World, _ring: Entity) -> Option<u32> {
+        None
+    }
+
+    fn cost(&self, world: &World, ring: Entity, offset: u32) -> Option<u32> {
+        let index = world.get::<Ring>(ring)?.index;
+        let duration = cycle_duration_after(Self::duration(world, ring), offset);
+        Some(costs::compute_cycle_cost(index, duration))
+    }
+
+    fn apply(&self, world: &mut World, entity: Entity, levels: u32) {
+        let Some(mut ring) = world.get_mut::<Ring>(entity) else {
+            return;
+        };
+        let duration = cycle_duration_after(ring.cycle_timer.as_ref().map(Timer::duration), levels)
+            .unwrap_or(INITIAL_CYCLE_TIME);
+        if let Some(timer) = ring.cycle_timer.as_mut() {
+            timer.set_duration(duration);
+        } else {
+            ring.cycle_timer = Some(Timer::new(duration, TimerMode::Repeating));
+        }
+    }
+
+    fn value_text(&self, world: &World, ring: Entity) -> String {
+        match Self::duration(world, ring) {
+            Some(duration) => format!("{:.2}s", duration.as_secs_f32()),
+            None => " ".to_string(),
+        }
+    }
+}
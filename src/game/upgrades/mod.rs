@@ -1,206 +1,310 @@
+//! Upgrades the player can buy with cycles.
+//!
+//! Every upgrade implements [`Upgrade`] and is registered with
+//! [`RegisterUpgrade::register_upgrade`]. Pressing a button with an [`UpgradeButton`]
+//! triggers a [`PurchaseRequested`], which runs through a single purchase pipeline and
+//! reports back with [`PurchaseCompleted`] or [`PurchaseFailed`]. The upgrades panel builds
+//! its buttons from the [`Upgrades`] registry, so a new upgrade needs no systems of its own.
+
 pub mod costs;
+mod kinds;
+pub mod transactions;
+
+use std::sync::Arc;
 
-use super::{cycles::CycleCount, movement::Revolve};
-use crate::game::assets::{HandleMap, ImageKey};
-use crate::game::spawn::atom::{AddProton, AddProtonNeutron, Atom, Electron, ElectronBundle, Ring};
 use bevy::prelude::*;
-use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
-use std::time::Duration;
+
+use super::cycles::CycleCount;
+use crate::game::spawn::atom::CurrentAtom;
+use costs::BulkCost;
+pub use kinds::{
+    cycle_duration_after, next_cycle_duration, BuyElectron, BuyNextRing, CycleUpgrade,
+    SpeedUpgrade, INITIAL_REVOLVE_SPEED, MAX_RINGS, SPEED_UPGRADE_INCREMENT,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(
-        Update,
-        (
-            apply_electron_upgrade,
-            apply_cycle_upgrade,
-            apply_speed_upgrade,
-            apply_buy_ring,
-        ),
-    );
-}
-
-#[derive(Component)]
-pub struct BuyNextRing;
-#[derive(Component)]
-pub struct BuyElectron(pub Entity);
-#[derive(Component)]
-pub struct SpeedUpgrade(pub Entity);
-#[derive(Component)]
-pub struct CycleUpgrade(pub Entity);
-
-const MAX_RINGS: usize = 5;
-
-pub const INITIAL_REVOLVE_SPEED: f32 = 3.0;
-fn apply_buy_ring(
-    q_interaction: Query<(Entity, &Interaction), (With<BuyNextRing>, Changed<Interaction>)>,
-    mut cycle_count: ResMut<CycleCount>,
-    mut commands: Commands,
-    query_atom: Query<(Entity, Option<&Children>), With<Atom>>,
-    query_rings: Query<&Ring>,
+    app.register_type::<PurchaseMultiplier>();
+    app.init_resource::<PurchaseMultiplier>();
+    app.init_resource::<Upgrades>();
 
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-) {
-    for (entity, interaction) in &q_interaction {
-        if interaction != &Interaction::Pressed {
-            continue;
-        }
-        let (atom, maybe_children) = query_atom.get_single().unwrap();
-        let ring_count = maybe_children
-            .map(|children| {
-                children
-                    .iter()
-                    .filter(|child| query_rings.get(**child).is_ok())
-                    .count()
-            })
-            .unwrap_or_default();
-
-        if ring_count + 1 == MAX_RINGS {
-            log::info!("Last Ring purchased");
-            commands.entity(entity).despawn_recursive();
-        }
+    // The panel lists upgrades in the order they are registered.
+    app.register_upgrade(BuyNextRing)
+        .register_upgrade(BuyElectron)
+        .register_upgrade(SpeedUpgrade)
+        .register_upgrade(CycleUpgrade);
 
-        let cost = costs::compute_ring_cost(ring_count);
-        if cost > cycle_count.0 {
-            log::info!("Cannot afford ring: not enough cycles");
-            continue;
-        }
+    app.add_plugins(transactions::plugin);
+    app.observe(handle_purchase_request);
+    app.add_systems(Update, request_pressed_upgrades);
+}
 
-        cycle_count.0 -= cost;
-
-        let ring = Ring::new(ring_count);
-        let ring_radius = ring.radius();
-        commands.entity(atom).with_children(|parent| {
-            parent.spawn((
-                ring,
-                MaterialMesh2dBundle {
-                    mesh: Mesh2dHandle(meshes.add(Circle::new(ring_radius))),
-                    material: materials.add(Color::srgba_u8(0x28, 0x66, 0x6e, 0x66)),
-                    transform: Transform::from_xyz(0., 0., -100.),
-                    ..default()
-                },
-                Revolve::new(INITIAL_REVOLVE_SPEED),
-            ));
-        });
-    }
+/// Identifies an upgrade in the [`Upgrades`] registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpgradeId(pub &'static str);
+
+/// What an upgrade is bought for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UpgradeTarget {
+    /// The upgrade applies to the whole atom.
+    Atom,
+    /// The upgrade applies to a single ring, and is listed under that ring.
+    Ring,
 }
 
-fn apply_speed_upgrade(
-    q_interaction: Query<(&Interaction, &SpeedUpgrade), Changed<Interaction>>,
-    mut cycle_count: ResMut<CycleCount>,
+/// Describes an upgrade the player can buy.
+///
+/// `target` is the atom or ring entity the upgrade is bought for, see [`Upgrade::target`].
+pub trait Upgrade: Send + Sync + 'static {
+    fn id(&self) -> UpgradeId;
 
-    mut query_ring: Query<(&Ring, &mut Revolve)>,
-) {
-    for (interaction, entity) in &q_interaction {
-        if interaction != &Interaction::Pressed {
-            continue;
-        }
+    /// Name shown on the upgrade's button.
+    fn name(&self) -> &'static str;
 
-        let Ok((ring, mut revolve)) = query_ring.get_mut(entity.0) else {
-            continue;
-        };
+    fn target(&self) -> UpgradeTarget;
 
-        let cost = costs::compute_speed_cost(ring.index, revolve.level);
-        if cost > cycle_count.0 {
-            log::info!("Cannot afford speed upgrade: not enough cycles");
-            continue;
-        }
+    /// Levels left to buy, or `None` if there is no limit.
+    fn remaining(&self, world: &World, target: Entity) -> Option<u32>;
+
+    /// The cost of the level `offset` levels beyond the current one, or `None` if `target` can
+    /// no longer be upgraded, for instance because the ring it was bought for is gone.
+    fn cost(&self, world: &World, target: Entity, offset: u32) -> Option<u32>;
 
-        revolve.speed += 1.0;
-        revolve.level += 1;
-        cycle_count.0 -= cost;
+    /// Buys `levels` levels. The cost has already been paid.
+    fn apply(&self, world: &mut World, target: Entity, levels: u32);
+
+    /// The current value shown on the upgrade's button.
+    fn value_text(&self, _world: &World, _target: Entity) -> String {
+        String::new()
+    }
+
+    /// Shown in place of the cost once there are no levels left to buy.
+    fn maxed_text(&self) -> &'static str {
+        "Max"
     }
 }
 
-const INITIAL_CYCLE_TIME: Duration = Duration::from_secs(3);
-fn apply_cycle_upgrade(
-    q_interaction: Query<(&Interaction, &CycleUpgrade), Changed<Interaction>>,
-    mut cycle_count: ResMut<CycleCount>,
+/// Every registered [`Upgrade`], in registration order.
+#[derive(Resource, Default, Clone)]
+pub struct Upgrades(Vec<Arc<dyn Upgrade>>);
 
-    mut query_ring: Query<&mut Ring>,
-) {
-    for (interaction, entity) in &q_interaction {
-        if interaction != &Interaction::Pressed {
-            continue;
-        }
+impl Upgrades {
+    pub fn get(&self, id: UpgradeId) -> Option<Arc<dyn Upgrade>> {
+        self.0.iter().find(|upgrade| upgrade.id() == id).cloned()
+    }
 
-        let Ok(mut ring) = query_ring.get_mut(entity.0) else {
-            continue;
-        };
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Upgrade>> {
+        self.0.iter()
+    }
 
-        let cost = costs::compute_cycle_cost(
-            ring.index,
-            ring.cycle_timer.as_ref().map(|timer| timer.duration()),
+    /// Every upgrade bought for `target`.
+    pub fn for_target(&self, target: UpgradeTarget) -> impl Iterator<Item = &Arc<dyn Upgrade>> {
+        self.iter()
+            .filter(move |upgrade| upgrade.target() == target)
+    }
+}
+
+/// An extension trait for registering upgrades on the [`App`].
+pub trait RegisterUpgrade {
+    fn register_upgrade(&mut self, upgrade: impl Upgrade) -> &mut Self;
+}
+
+impl RegisterUpgrade for App {
+    fn register_upgrade(&mut self, upgrade: impl Upgrade) -> &mut Self {
+        let mut upgrades = self
+            .world_mut()
+            .get_resource_or_insert_with(Upgrades::default);
+        assert!(
+            upgrades.get(upgrade.id()).is_none(),
+            "upgrade {:?} registered twice",
+            upgrade.id()
         );
-        if cost > cycle_count.0 {
-            log::info!("Cannot afford cycle speed upgrade: not enough cycles");
-            continue;
+        upgrades.0.push(Arc::new(upgrade));
+        self
+    }
+}
+
+/// Pressing a button with this component buys the upgrade.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct UpgradeButton {
+    pub upgrade: UpgradeId,
+    /// The ring the upgrade is bought for, or `None` for [`UpgradeTarget::Atom`] upgrades.
+    pub ring: Option<Entity>,
+}
+
+/// How many levels a single press of an upgrade button buys.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum PurchaseMultiplier {
+    #[default]
+    One,
+    Ten,
+    Hundred,
+    /// As many levels as can be afforded.
+    Max,
+}
+
+impl PurchaseMultiplier {
+    pub const ALL: [Self; 4] = [Self::One, Self::Ten, Self::Hundred, Self::Max];
+
+    /// The number of levels bought, or `None` for [`PurchaseMultiplier::Max`].
+    pub fn levels(&self) -> Option<u32> {
+        match self {
+            Self::One => Some(1),
+            Self::Ten => Some(10),
+            Self::Hundred => Some(100),
+            Self::Max => None,
         }
+    }
 
-        if let Some(timer) = ring.cycle_timer.as_mut() {
-            timer.set_duration(timer.duration() * 4 / 5);
-        } else {
-            ring.cycle_timer = Some(Timer::new(INITIAL_CYCLE_TIME, TimerMode::Repeating));
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::One => "x1",
+            Self::Ten => "x10",
+            Self::Hundred => "x100",
+            Self::Max => "Max",
         }
+    }
+}
 
-        cycle_count.0 -= cost;
+/// The entity an upgrade button buys for.
+pub fn resolve_target(
+    world: &World,
+    upgrade: &dyn Upgrade,
+    ring: Option<Entity>,
+) -> Option<Entity> {
+    match upgrade.target() {
+        UpgradeTarget::Atom => world.resource::<CurrentAtom>().0,
+        UpgradeTarget::Ring => ring.filter(|ring| world.get_entity(*ring).is_some()),
     }
 }
-fn apply_electron_upgrade(
-    mut commands: Commands,
-    q_interaction: Query<(&Interaction, &BuyElectron), Changed<Interaction>>,
-    mut cycle_count: ResMut<CycleCount>,
 
-    image_handles: Res<HandleMap<ImageKey>>,
-    query_ring: Query<(Entity, Option<&Children>, &Ring)>,
-    query_electrons: Query<(&Parent, &Electron)>,
-) {
-    for (interaction, entity) in &q_interaction {
-        if interaction != &Interaction::Pressed {
-            continue;
+/// The levels, and their summed cost, that the next purchase of `upgrade` would buy.
+/// Returns `None` if there are no levels left to buy.
+pub fn quote_upgrade(
+    world: &World,
+    upgrade: &dyn Upgrade,
+    target: Entity,
+    multiplier: PurchaseMultiplier,
+    budget: u32,
+) -> Option<BulkCost> {
+    let level_costs = (0..).map_while(|offset| upgrade.cost(world, target, offset));
+    match upgrade.remaining(world, target) {
+        Some(remaining) => {
+            costs::compute_bulk_cost(level_costs.take(remaining as usize), multiplier, budget)
         }
+        None => costs::compute_bulk_cost(level_costs, multiplier, budget),
+    }
+}
 
-        let Ok((parent, maybe_children, ring)) = query_ring.get(entity.0) else {
-            continue;
-        };
-
-        let mut electron_count = 0;
-        if let Some(children) = maybe_children {
-            for child in children {
-                if query_electrons.get(*child).is_ok() {
-                    electron_count += 1;
-                }
-            }
-        }
+/// Trigger this event to buy `upgrade` for `ring` using the current [`PurchaseMultiplier`].
+/// The outcome is reported with [`PurchaseCompleted`] or [`PurchaseFailed`].
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PurchaseRequested {
+    pub upgrade: UpgradeId,
+    /// The ring the upgrade is bought for, or `None` for [`UpgradeTarget::Atom`] upgrades.
+    pub ring: Option<Entity>,
+}
 
-        if electron_count >= ring.max_electrons {
-            log::info!("Ring {} is full", ring.index);
-            continue;
-        }
+/// Triggered after an upgrade has been paid for and applied.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PurchaseCompleted {
+    pub upgrade: UpgradeId,
+    pub ring: Option<Entity>,
+    pub levels: u32,
+    pub cost: u32,
+}
 
-        let cost = costs::compute_electron_cost(ring.index, electron_count);
-        if cost > cycle_count.0 {
-            log::info!("Cannot afford electron: not enough cycles");
-            continue;
+/// Triggered when a [`PurchaseRequested`] could not be carried out.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PurchaseFailed {
+    pub upgrade: UpgradeId,
+    pub ring: Option<Entity>,
+    /// The quoted cost, if the upgrade could be priced.
+    pub cost: Option<u32>,
+    pub reason: PurchaseFailure,
+}
+
+/// Why a purchase failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PurchaseFailure {
+    UnknownUpgrade,
+    /// The atom or ring the upgrade is bought for no longer exists.
+    MissingTarget,
+    /// There are no levels left to buy.
+    Maxed,
+    NotEnoughCycles,
+}
+
+impl PurchaseFailure {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::UnknownUpgrade => "unknown upgrade",
+            Self::MissingTarget => "nothing to upgrade",
+            Self::Maxed => "nothing left to buy",
+            Self::NotEnoughCycles => "not enough cycles",
         }
+    }
+}
+
+fn handle_purchase_request(trigger: Trigger<PurchaseRequested>, mut commands: Commands) {
+    let request = *trigger.event();
+    commands.add(move |world: &mut World| purchase_upgrade(world, request));
+}
 
-        commands.entity(parent).with_children(|parent| {
-            parent.spawn(ElectronBundle::new(
-                ring.index,
-                electron_count,
-                ring.radius(),
-                image_handles.as_ref(),
-            ));
+/// Runs the purchase pipeline for `request`, triggering [`PurchaseCompleted`] or
+/// [`PurchaseFailed`].
+pub fn purchase_upgrade(world: &mut World, request: PurchaseRequested) {
+    let PurchaseRequested { upgrade: id, ring } = request;
+    let fail = |world: &mut World, cost: Option<u32>, reason: PurchaseFailure| {
+        log::info!("Cannot buy {}: {}", id.0, reason.description());
+        world.trigger(PurchaseFailed {
+            upgrade: id,
+            ring,
+            cost,
+            reason,
         });
+    };
 
-        if ring.index == 0 && electron_count == 0 {
-            commands.trigger(AddProton);
-        } else {
-            commands.trigger(AddProtonNeutron);
-        }
+    let Some(upgrade) = world.resource::<Upgrades>().get(id) else {
+        fail(world, None, PurchaseFailure::UnknownUpgrade);
+        return;
+    };
+    let Some(target) = resolve_target(world, upgrade.as_ref(), ring) else {
+        fail(world, None, PurchaseFailure::MissingTarget);
+        return;
+    };
+
+    let multiplier = *world.resource::<PurchaseMultiplier>();
+    let budget = world.resource::<CycleCount>().0;
+    let Some(bulk) = quote_upgrade(world, upgrade.as_ref(), target, multiplier, budget) else {
+        fail(world, None, PurchaseFailure::Maxed);
+        return;
+    };
+    if bulk.cost > budget {
+        fail(world, Some(bulk.cost), PurchaseFailure::NotEnoughCycles);
+        return;
+    }
 
-        cycle_count.0 -= cost;
+    world.resource_mut::<CycleCount>().0 -= bulk.cost;
+    upgrade.apply(world, target, bulk.levels);
+    world.trigger(PurchaseCompleted {
+        upgrade: id,
+        ring,
+        levels: bulk.levels,
+        cost: bulk.cost,
+    });
+}
 
-        //success sub money
+fn request_pressed_upgrades(
+    mut commands: Commands,
+    query_button: Query<(&Interaction, &UpgradeButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in &query_button {
+        if *interaction == Interaction::Pressed {
+            commands.trigger(PurchaseRequested {
+                upgrade: button.upgrade,
+                ring: button.ring,
+            });
+        }
     }
 }
@@ -0,0 +1,100 @@
+//! A bounded history of purchases
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use super::{PurchaseCompleted, PurchaseFailed, PurchaseFailure, UpgradeId, Upgrades};
+use crate::game::{replay::ReplayClock, spawn::atom::Ring};
+use crate::screen::InRun;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TransactionLog>();
+    app.init_resource::<TransactionLog>();
+    app.add_systems(OnEnter(InRun), reset_transaction_log);
+    app.observe(record_completed).observe(record_failed);
+}
+
+/// How many transactions are kept.
+pub const TRANSACTION_LOG_LEN: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Transaction {
+    /// Game time since the run started when the purchase was made, see [`ReplayClock`].
+    pub time: Duration,
+    pub upgrade: &'static str,
+    /// The index of the ring the upgrade was bought for.
+    pub ring: Option<usize>,
+    pub levels: u32,
+    pub cost: Option<u32>,
+    /// `None` if the purchase went through.
+    pub failure: Option<PurchaseFailure>,
+}
+
+/// The most recent purchases, oldest first.
+#[derive(Resource, Debug, Default, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct TransactionLog(VecDeque<Transaction>);
+
+impl TransactionLog {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Transaction> {
+        self.0.iter()
+    }
+
+    fn push(&mut self, transaction: Transaction) {
+        if self.0.len() == TRANSACTION_LOG_LEN {
+            self.0.pop_front();
+        }
+        self.0.push_back(transaction);
+    }
+}
+
+fn reset_transaction_log(mut log: ResMut<TransactionLog>) {
+    log.0.clear();
+}
+
+fn upgrade_name(upgrades: &Upgrades, id: UpgradeId) -> &'static str {
+    upgrades.get(id).map_or(id.0, |upgrade| upgrade.name())
+}
+
+fn record_completed(
+    trigger: Trigger<PurchaseCompleted>,
+    clock: Res<ReplayClock>,
+    upgrades: Res<Upgrades>,
+    query_ring: Query<&Ring>,
+    mut log: ResMut<TransactionLog>,
+) {
+    let event = trigger.event();
+    log.push(Transaction {
+        time: clock.elapsed(),
+        upgrade: upgrade_name(&upgrades, event.upgrade),
+        ring: event
+            .ring
+            .and_then(|ring| query_ring.get(ring).ok())
+            .map(|ring| ring.index),
+        levels: event.levels,
+        cost: Some(event.cost),
+        failure: None,
+    });
+}
+
+fn record_failed(
+    trigger: Trigger<PurchaseFailed>,
+    clock: Res<ReplayClock>,
+    upgrades: Res<Upgrades>,
+    query_ring: Query<&Ring>,
+    mut log: ResMut<TransactionLog>,
+) {
+    let event = trigger.event();
+    log.push(Transaction {
+        time: clock.elapsed(),
+        upgrade: upgrade_name(&upgrades, event.upgrade),
+        ring: event
+            .ring
+            .and_then(|ring| query_ring.get(ring).ok())
+            .map(|ring| ring.index),
+        levels: 0,
+        cost: event.cost,
+        failure: Some(event.reason),
+    });
+}
@@ -0,0 +1,100 @@
+//! Decides when a run has been won
+
+use bevy::prelude::*;
+
+use crate::game::spawn::atom::{Electron, Proton, Ring};
+use crate::game::stats::CycleStats;
+use crate::game::upgrades::MAX_RINGS;
+use crate::screen::{InRun, Screen};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<VictoryCondition>();
+    app.init_resource::<VictoryCondition>();
+    app.init_resource::<RunOutcome>();
+    app.add_systems(OnEnter(InRun), reset_run_outcome);
+    app.add_systems(
+        Update,
+        check_victory
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// What has to be done to win a run.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum VictoryCondition {
+    /// Buy every ring and fill them all with electrons.
+    #[default]
+    AllRingsFilled,
+    /// Build an atom with at least this many protons.
+    ReachElement(usize),
+}
+
+/// A snapshot of the run when it was won.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    /// Seconds played before winning.
+    pub duration: f32,
+    pub total_cycles: u64,
+    pub purchases: u32,
+    /// The number of protons in the atom.
+    pub element: usize,
+}
+
+/// Whether the current run has been won.
+/// Once it has, the run carries on in endless mode if the player continues.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RunOutcome {
+    pub summary: Option<RunSummary>,
+}
+
+fn reset_run_outcome(mut outcome: ResMut<RunOutcome>) {
+    outcome.summary = None;
+}
+
+fn check_victory(
+    condition: Res<VictoryCondition>,
+    stats: Res<CycleStats>,
+    mut outcome: ResMut<RunOutcome>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    query_ring: Query<(&Ring, Option<&Children>)>,
+    query_electrons: Query<(), With<Electron>>,
+    query_protons: Query<(), With<Proton>>,
+) {
+    if outcome.summary.is_some() {
+        return;
+    }
+
+    let element = query_protons.iter().count();
+    let won = match *condition {
+        VictoryCondition::AllRingsFilled => {
+            query_ring.iter().count() == MAX_RINGS
+                && query_ring.iter().all(|(ring, children)| {
+                    let electrons = children
+                        .map(|children| {
+                            children
+                                .iter()
+                                .filter(|child| query_electrons.contains(**child))
+                                .count()
+                        })
+                        .unwrap_or_default();
+                    electrons >= ring.max_electrons
+                })
+        }
+        VictoryCondition::ReachElement(protons) => element >= protons,
+    };
+    if !won {
+        return;
+    }
+
+    log::info!("Run won after {:.0}s", stats.elapsed());
+    outcome.summary = Some(RunSummary {
+        duration: stats.elapsed(),
+        total_cycles: stats.total(),
+        purchases: stats.purchases(),
+        element,
+    });
+    next_screen.set(Screen::Victory);
+}
@@ -0,0 +1,358 @@
+//! Named player actions and the keys, mouse buttons and gamepad buttons bound to them.
+//!
+//! Systems ask [`ActionInput`] whether an [`InputAction`] was pressed instead of checking
+//! keys directly, so every action can be rebound through [`InputBindings`].
+//! Bindings are saved whenever they change.
+
+use std::fmt;
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, Enum},
+    utils::HashMap,
+};
+
+use crate::persistence::DataDir;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputAction>();
+    let bindings = InputBindings::load(app.world().resource::<DataDir>());
+    app.insert_resource(bindings);
+    app.add_systems(
+        Update,
+        save_bindings.run_if(
+            resource_changed::<InputBindings>.and_then(not(resource_added::<InputBindings>)),
+        ),
+    );
+}
+
+/// Something the player can do with a key or button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum InputAction {
+    /// Spin the electrons by hand.
+    ManualCycle,
+    FocusUp,
+    FocusDown,
+    FocusLeft,
+    FocusRight,
+    /// Press the focused button.
+    Activate,
+    /// Leave the current screen.
+    Back,
+    /// Show outlines around every UI node.
+    #[cfg(feature = "dev")]
+    ToggleUiOverlay,
+    /// Open the debug console.
+    #[cfg(feature = "dev")]
+    ToggleConsole,
+    /// Open the entity and resource inspector.
+    #[cfg(feature = "dev")]
+    ToggleInspector,
+}
+
+impl InputAction {
+    /// Every action, leaving out the dev tools' outside of dev builds.
+    pub const ALL: &'static [Self] = &[
+        Self::ManualCycle,
+        Self::FocusUp,
+        Self::FocusDown,
+        Self::FocusLeft,
+        Self::FocusRight,
+        Self::Activate,
+        Self::Back,
+        #[cfg(feature = "dev")]
+        Self::ToggleUiOverlay,
+        #[cfg(feature = "dev")]
+        Self::ToggleConsole,
+        #[cfg(feature = "dev")]
+        Self::ToggleInspector,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ManualCycle => "Spin electrons",
+            Self::FocusUp => "Focus up",
+            Self::FocusDown => "Focus down",
+            Self::FocusLeft => "Focus left",
+            Self::FocusRight => "Focus right",
+            Self::Activate => "Press button",
+            Self::Back => "Back",
+            #[cfg(feature = "dev")]
+            Self::ToggleUiOverlay => "UI debug overlay",
+            #[cfg(feature = "dev")]
+            Self::ToggleConsole => "Debug console",
+            #[cfg(feature = "dev")]
+            Self::ToggleInspector => "Inspector",
+        }
+    }
+}
+
+/// A single input that can trigger an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// A button on any connected gamepad.
+    Gamepad(GamepadButtonType),
+}
+
+/// The devices a binding can come from.
+/// Each action can have its own bindings for each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum BindingKind {
+    KeyboardMouse,
+    Gamepad,
+}
+
+impl InputBinding {
+    pub fn kind(self) -> BindingKind {
+        match self {
+            Self::Key(_) | Self::Mouse(_) => BindingKind::KeyboardMouse,
+            Self::Gamepad(_) => BindingKind::Gamepad,
+        }
+    }
+
+    /// Writes the binding as `Device:Variant`, which [`InputBinding::parse`] reads back.
+    fn to_config(self) -> String {
+        match self {
+            Self::Key(key) => format!("Key:{}", key.variant_name()),
+            Self::Mouse(button) => format!("Mouse:{}", button.variant_name()),
+            Self::Gamepad(button) => format!("Gamepad:{}", button.variant_name()),
+        }
+    }
+
+    fn parse(config: &str) -> Option<Self> {
+        let (device, name) = config.trim().split_once(':')?;
+        match device {
+            "Key" => unit_variant(name).map(Self::Key),
+            "Mouse" => unit_variant(name).map(Self::Mouse),
+            "Gamepad" => unit_variant(name).map(Self::Gamepad),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => {
+                let name = key.variant_name();
+                let name = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(name);
+                write!(f, "{name}")
+            }
+            Self::Mouse(button) => write!(f, "Mouse {}", button.variant_name()),
+            Self::Gamepad(button) => write!(f, "Pad {}", button.variant_name()),
+        }
+    }
+}
+
+/// Builds a fieldless enum variant from its name.
+fn unit_variant<T: FromReflect>(name: &str) -> Option<T> {
+    T::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+}
+
+/// One input bound to more than one action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingConflict {
+    pub binding: InputBinding,
+    pub actions: Vec<InputAction>,
+}
+
+/// The inputs bound to each action.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputBindings(HashMap<InputAction, Vec<InputBinding>>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GamepadButtonType as Pad;
+        use InputAction::*;
+        use InputBinding::{Gamepad, Key};
+
+        Self(HashMap::from([
+            (
+                ManualCycle,
+                vec![
+                    Key(KeyCode::Space),
+                    Gamepad(Pad::West),
+                    Gamepad(Pad::RightTrigger2),
+                ],
+            ),
+            (FocusUp, vec![Key(KeyCode::ArrowUp), Gamepad(Pad::DPadUp)]),
+            (
+                FocusDown,
+                vec![Key(KeyCode::ArrowDown), Gamepad(Pad::DPadDown)],
+            ),
+            (
+                FocusLeft,
+                vec![Key(KeyCode::ArrowLeft), Gamepad(Pad::DPadLeft)],
+            ),
+            (
+                FocusRight,
+                vec![Key(KeyCode::ArrowRight), Gamepad(Pad::DPadRight)],
+            ),
+            (
+                Activate,
+                vec![
+                    Key(KeyCode::Enter),
+                    Key(KeyCode::NumpadEnter),
+                    Gamepad(Pad::South),
+                ],
+            ),
+            (Back, vec![Key(KeyCode::Escape), Gamepad(Pad::East)]),
+            #[cfg(feature = "dev")]
+            (ToggleUiOverlay, vec![Key(KeyCode::F1)]),
+            #[cfg(feature = "dev")]
+            (ToggleConsole, vec![Key(KeyCode::Backquote)]),
+            #[cfg(feature = "dev")]
+            (ToggleInspector, vec![Key(KeyCode::F2)]),
+        ]))
+    }
+}
+
+impl InputBindings {
+    const SAVE_KEY: &'static str = "bindings";
+
+    pub fn get(&self, action: InputAction) -> &[InputBinding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Replaces an action's bindings of the same kind as `binding`.
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|bound| bound.kind() != binding.kind());
+        bindings.push(binding);
+    }
+
+    /// Every input that is bound to more than one action, in [`InputAction::ALL`] order.
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let mut conflicts: Vec<BindingConflict> = Vec::new();
+        for &action in InputAction::ALL {
+            for &binding in self.get(action) {
+                match conflicts
+                    .iter_mut()
+                    .find(|conflict| conflict.binding == binding)
+                {
+                    Some(conflict) => conflict.actions.push(action),
+                    None => conflicts.push(BindingConflict {
+                        binding,
+                        actions: vec![action],
+                    }),
+                }
+            }
+        }
+        conflicts.retain(|conflict| conflict.actions.len() > 1);
+        conflicts
+    }
+
+    /// Loads the saved bindings on top of the defaults.
+    /// Actions that weren't saved keep their default bindings.
+    fn load(data_dir: &DataDir) -> Self {
+        let mut bindings = Self::default();
+        let saved = data_dir.load(Self::SAVE_KEY).unwrap_or_default();
+        for (name, value) in saved.lines().filter_map(|line| line.split_once('=')) {
+            let Some(action) = InputAction::ALL
+                .iter()
+                .copied()
+                .find(|action| action.variant_name() == name.trim())
+            else {
+                log::warn!("Ignoring bindings for unknown action {name}");
+                continue;
+            };
+            let parsed = value
+                .split(',')
+                .filter(|binding| !binding.trim().is_empty())
+                .filter_map(|binding| {
+                    let parsed = InputBinding::parse(binding);
+                    if parsed.is_none() {
+                        log::warn!("Ignoring unknown binding {binding}");
+                    }
+                    parsed
+                })
+                .collect();
+            bindings.0.insert(action, parsed);
+        }
+        for conflict in bindings.conflicts() {
+            log::warn!(
+                "{} is bound to several actions: {:?}",
+                conflict.binding,
+                conflict.actions
+            );
+        }
+        bindings
+    }
+
+    fn save(&self, data_dir: &DataDir) {
+        let mut contents = String::new();
+        for &action in InputAction::ALL {
+            let bindings: Vec<_> = self
+                .get(action)
+                .iter()
+                .map(|binding| binding.to_config())
+                .collect();
+            contents += &format!("{}={}\n", action.variant_name(), bindings.join(","));
+        }
+        data_dir.save(Self::SAVE_KEY, &contents);
+    }
+}
+
+fn save_bindings(bindings: Res<InputBindings>, data_dir: Res<DataDir>) {
+    bindings.save(&data_dir);
+}
+
+/// Reads the state of [`InputAction`]s through the current [`InputBindings`].
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, InputBindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+}
+
+impl ActionInput<'_> {
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.bindings
+            .get(action)
+            .iter()
+            .any(|binding| match *binding {
+                InputBinding::Key(key) => self.keys.just_pressed(key),
+                InputBinding::Mouse(button) => self.mouse.just_pressed(button),
+                InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| {
+                    self.gamepad_buttons
+                        .just_pressed(GamepadButton::new(gamepad, button))
+                }),
+            })
+    }
+
+    /// The first input of the given kind pressed this frame, for rebinding an action.
+    pub fn any_just_pressed(&self, kind: BindingKind) -> Option<InputBinding> {
+        match kind {
+            BindingKind::KeyboardMouse => self
+                .keys
+                .get_just_pressed()
+                .next()
+                .map(|key| InputBinding::Key(*key))
+                .or_else(|| {
+                    self.mouse
+                        .get_just_pressed()
+                        .next()
+                        .map(|button| InputBinding::Mouse(*button))
+                }),
+            BindingKind::Gamepad => self
+                .gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Gamepad(button.button_type)),
+        }
+    }
+}
+
+/// A run condition that is true on the frame an action is pressed.
+pub fn action_just_pressed(action: InputAction) -> impl Fn(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
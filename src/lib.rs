@@ -1,65 +1,181 @@
 #[cfg(feature = "dev")]
 mod dev_tools;
-mod game;
-mod screen;
+pub mod game;
+mod input;
+pub mod offscreen;
+pub mod persistence;
+pub mod screen;
+pub mod settings;
 mod ui;
 
+use std::{path::PathBuf, time::Duration};
+
 use bevy::{
+    app::ScheduleRunnerPlugin,
     asset::AssetMetaCheck,
     audio::{AudioPlugin, Volume},
     prelude::*,
+    render::pipelined_rendering::PipelinedRenderingPlugin,
+    render::{
+        settings::{Backends, RenderCreation, WgpuSettings},
+        RenderPlugin,
+    },
+    window::ExitCondition,
+    winit::WinitPlugin,
 };
 use bevy_mod_picking::prelude::*;
 
-pub struct AppPlugin;
+use game::rng::GameRng;
+use persistence::DataDir;
+
+/// The whole game, including Bevy's own plugins.
+///
+/// `AppPlugin::default()` opens a window and plays audio. Tests, the simulator and CI can
+/// turn those off to run the game's logic on machines without a GPU or audio device.
+#[derive(Debug, Clone)]
+pub struct AppPlugin {
+    /// Run without a window or renderer, stepping the game on a timer instead.
+    /// Images are replaced with blank stand-ins since they can't be loaded without a renderer.
+    pub headless: bool,
+    /// When headless, render to an offscreen image of this size anyway, so frames can be
+    /// captured with [`OffscreenTarget`](offscreen::OffscreenTarget). Any GPU backend will do,
+    /// including software ones like llvmpipe.
+    pub offscreen: Option<UVec2>,
+    /// Play sound. Without it, no audio device is opened and sounds are stand-ins.
+    pub audio: bool,
+    /// Seed for [`GameRng`](game::rng::GameRng), so runs can be repeated. Random if unset.
+    pub seed: Option<u64>,
+    /// Where to save player data, instead of the default [`DataDir`](persistence::DataDir).
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Default for AppPlugin {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            offscreen: None,
+            audio: true,
+            seed: None,
+            data_dir: None,
+        }
+    }
+}
+
+impl AppPlugin {
+    /// The game without a window, renderer or audio.
+    pub fn headless() -> Self {
+        Self {
+            headless: true,
+            offscreen: None,
+            audio: false,
+            seed: None,
+            data_dir: None,
+        }
+    }
+
+    /// The game without a window or audio, rendered to an offscreen image of `size`.
+    pub fn offscreen(size: UVec2) -> Self {
+        Self {
+            offscreen: Some(size),
+            ..Self::headless()
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_data_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.data_dir = Some(dir.into());
+        self
+    }
+}
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
-        // Order new `AppStep` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (AppSet::TickTimers, AppSet::RecordInput, AppSet::Update).chain(),
-        );
+        let render = !self.headless || self.offscreen.is_some();
+        app.insert_resource(AppConfig {
+            headless: self.headless,
+            render,
+            audio: self.audio,
+        });
+        app.insert_resource(GameRng::new(self.seed));
+        app.insert_resource(DataDir::new(self.data_dir.clone()));
 
         // Spawn the main camera.
         app.add_systems(Startup, spawn_camera);
 
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
-                    ..default()
-                })
+        let mut plugins = DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics on web build on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(AudioPlugin {
+                global_volume: GlobalVolume {
+                    volume: Volume::new(0.3),
+                },
+                ..default()
+            });
+        plugins = if self.headless {
+            plugins
                 .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "atomiccycles".to_string(),
-                        canvas: Some("#bevy".to_string()),
-                        fit_canvas_to_parent: true,
-                        prevent_default_event_handling: true,
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        // Don't look for a GPU at all unless rendering offscreen, and then
+                        // accept the OpenGL backend too, which software renderers provide.
+                        backends: render.then_some(Backends::all()),
                         ..default()
-                    }
-                    .into(),
-                    ..default()
+                    }),
+                    // Compile shaders before drawing, so no frame is missing anything.
+                    synchronous_pipeline_compilation: true,
                 })
-                .set(AudioPlugin {
-                    global_volume: GlobalVolume {
-                        volume: Volume::new(0.3),
-                    },
+                // Render each frame within its own update, so captures show that update.
+                .disable::<PipelinedRenderingPlugin>()
+                .disable::<WinitPlugin>()
+                .add(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+                    1. / 60.,
+                )))
+        } else {
+            plugins.set(WindowPlugin {
+                primary_window: Window {
+                    title: "atomiccycles".to_string(),
+                    canvas: Some("#bevy".to_string()),
+                    fit_canvas_to_parent: true,
+                    prevent_default_event_handling: true,
                     ..default()
-                }),
-        )
-        .add_plugins(
+                }
+                .into(),
+                ..default()
+            })
+        };
+        if !self.audio {
+            plugins = plugins.disable::<AudioPlugin>();
+        }
+        app.add_plugins(plugins).add_plugins(
             DefaultPickingPlugins
                 .build()
                 .disable::<DefaultHighlightingPlugin>(),
         );
+        if !self.audio {
+            // Sounds are still handed around, they just never play.
+            app.init_asset::<AudioSource>();
+        }
 
-        // Add other plugins.
-        app.add_plugins((game::plugin, screen::plugin, ui::plugin));
+        if let (true, Some(size)) = (self.headless, self.offscreen) {
+            app.add_plugins(offscreen::OffscreenPlugin { size });
+        }
+
+        // Add the game itself.
+        app.add_plugins(GamePlugin);
 
         // Enable dev tools for dev builds.
         #[cfg(feature = "dev")]
@@ -67,6 +183,50 @@ impl Plugin for AppPlugin {
     }
 }
 
+/// How [`AppPlugin`] was configured, for the parts of the game that depend on it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct AppConfig {
+    pub headless: bool,
+    /// Whether there is a renderer, in a window or offscreen.
+    pub render: bool,
+    pub audio: bool,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        let plugin = AppPlugin::default();
+        Self {
+            headless: plugin.headless,
+            render: !plugin.headless,
+            audio: plugin.audio,
+        }
+    }
+}
+
+/// The game's rules, screens and UI, without any of Bevy's own plugins.
+/// [`AppPlugin`] adds this on top of `DefaultPlugins`.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppConfig>();
+
+        // Order new `AppStep` variants by adding them here:
+        app.configure_sets(
+            Update,
+            (AppSet::TickTimers, AppSet::RecordInput, AppSet::Update).chain(),
+        );
+
+        app.add_plugins((
+            game::plugin,
+            input::plugin,
+            screen::plugin,
+            settings::plugin,
+            ui::plugin,
+        ));
+    }
+}
+
 /// High-level groupings of systems for the app in the `Update` schedule.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
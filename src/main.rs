@@ -1,9 +1,109 @@
 // Disable console on Windows for non-dev builds.
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
-use atomiccycles::AppPlugin;
-use bevy::prelude::*;
+use std::time::Duration;
+
+use atomiccycles::{
+    game::{
+        assets::AssetProgress,
+        cycles::CycleCount,
+        movement::Revolve,
+        replay::{Replay, ReplayClock, ReplayPlayback},
+        spawn::atom::{Electron, Proton, Ring},
+    },
+    screen::Screen,
+    AppPlugin,
+};
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+/// How far time moves on each update while playing back a replay.
+const REPLAY_FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
 
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [flag, path] if flag == "--replay" => play_replay(path),
+        _ => App::new().add_plugins(AppPlugin::default()).run(),
+    }
+}
+
+/// Plays back the replay at `path` without a window, as fast as possible, then prints how the
+/// run ended up. Handy for checking a bug report, or how a balance change plays out.
+fn play_replay(path: &str) -> AppExit {
+    let replay = match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            eprintln!("{error}");
+            return AppExit::error();
+        }
+    };
+    // Keep anything the run saves, like achievements, away from the player's own data.
+    let data_dir = std::env::temp_dir().join(format!("atomiccycles-replay-{}", std::process::id()));
+    let mut app = App::new();
+    app.add_plugins(
+        AppPlugin::headless()
+            .with_seed(replay.seed)
+            .with_data_dir(&data_dir),
+    );
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(REPLAY_FRAME));
+    app.insert_resource(ReplayPlayback::new(replay));
+    // `App::run` would do this, but the replay steps the app itself.
+    app.finish();
+    app.cleanup();
+
+    set_screen(&mut app, Screen::Loading);
+    while !app.world().resource::<AssetProgress>().is_done() {
+        if *app.world().resource::<State<Screen>>().get() == Screen::LoadingFailed {
+            eprintln!("Cannot play the replay, the game's assets failed to load");
+            return AppExit::error();
+        }
+        app.update();
+    }
+    set_screen(&mut app, Screen::Playing);
+    while !app.world().resource::<ReplayPlayback>().finished() {
+        app.update();
+    }
+    // Let the last action take effect.
+    app.update();
+
+    print_run(app.world_mut());
+    let _ = std::fs::remove_dir_all(data_dir);
+    AppExit::Success
+}
+
+fn set_screen(app: &mut App, screen: Screen) {
+    app.world_mut()
+        .resource_mut::<NextState<Screen>>()
+        .set(screen);
+    app.update();
+}
+
+fn print_run(world: &mut World) {
+    let time = world.resource::<ReplayClock>().elapsed();
+    println!("Time: {:.3}s", time.as_secs_f64());
+    println!("Cycles: {}", world.resource::<CycleCount>().0);
+    let protons = world
+        .query_filtered::<(), With<Proton>>()
+        .iter(world)
+        .count();
+    println!("Protons: {protons}");
+
+    let mut query_electron = world.query_filtered::<(), With<Electron>>();
+    let mut rings: Vec<_> = world
+        .query::<(&Ring, &Revolve, Option<&Children>)>()
+        .iter(world)
+        .map(|(ring, revolve, children)| (ring.index, revolve.level, children))
+        .collect();
+    rings.sort_by_key(|(index, _, _)| *index);
+    for (index, level, children) in rings {
+        let electrons = children
+            .into_iter()
+            .flatten()
+            .filter(|child| query_electron.get(world, **child).is_ok())
+            .count();
+        println!(
+            "Ring {}: {electrons} electrons, speed level {level}",
+            index + 1
+        );
+    }
 }
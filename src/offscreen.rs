@@ -0,0 +1,202 @@
+//! Renders the game to an image instead of a window, and reads frames back from it.
+//!
+//! Used by headless apps that still render, like the visual regression tests. Request a
+//! frame with [`OffscreenTarget::request_capture`] and collect it after the next update
+//! with [`OffscreenTarget::take_capture`].
+
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Mutex,
+};
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::{RenderAssetUsages, RenderAssets},
+        render_resource::{
+            BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+            ImageDataLayout, Maintain, MapMode, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::GpuImage,
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+/// The format frames are rendered in, and captured in.
+const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+/// Renders every camera to an image of `size`.
+pub(super) struct OffscreenPlugin {
+    pub size: UVec2,
+}
+
+impl Plugin for OffscreenPlugin {
+    fn build(&self, app: &mut App) {
+        let size = Extent3d {
+            width: self.size.x,
+            height: self.size.y,
+            depth_or_array_layers: 1,
+        };
+        let mut image = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[0; 4],
+            FORMAT,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage |=
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+        let image = app.world_mut().resource_mut::<Assets<Image>>().add(image);
+
+        let (sender, receiver) = channel();
+        app.insert_resource(OffscreenTarget {
+            image,
+            requests: 0,
+            receiver: Mutex::new(receiver),
+        });
+        app.add_systems(PostStartup, target_offscreen);
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app.insert_resource(RenderCapture {
+            image: AssetId::default(),
+            requests: 0,
+            captured: 0,
+            sender,
+        });
+        render_app.add_systems(ExtractSchedule, extract_capture_requests);
+        render_app.add_systems(
+            Render,
+            capture_frame
+                .after(RenderSet::Render)
+                .before(RenderSet::Cleanup),
+        );
+    }
+}
+
+/// The image the game is rendered to.
+#[derive(Resource)]
+pub struct OffscreenTarget {
+    image: Handle<Image>,
+    /// Captures requested so far.
+    requests: u32,
+    receiver: Mutex<Receiver<Image>>,
+}
+
+impl OffscreenTarget {
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+
+    /// Captures the frame rendered in the next update.
+    pub fn request_capture(&mut self) {
+        self.requests += 1;
+    }
+
+    /// The oldest captured frame not taken yet.
+    pub fn take_capture(&self) -> Option<Image> {
+        self.receiver.lock().ok()?.try_recv().ok()
+    }
+}
+
+fn target_offscreen(target: Res<OffscreenTarget>, mut query_camera: Query<&mut Camera>) {
+    for mut camera in &mut query_camera {
+        camera.target = RenderTarget::Image(target.image.clone());
+    }
+}
+
+/// [`OffscreenTarget`] in the render world.
+#[derive(Resource)]
+struct RenderCapture {
+    image: AssetId<Image>,
+    requests: u32,
+    /// Captures made so far.
+    captured: u32,
+    sender: Sender<Image>,
+}
+
+fn extract_capture_requests(
+    target: Extract<Res<OffscreenTarget>>,
+    mut capture: ResMut<RenderCapture>,
+) {
+    capture.image = target.image.id();
+    capture.requests = target.requests;
+}
+
+/// Copies the rendered frame back from the GPU, once rendering has finished.
+fn capture_frame(
+    mut capture: ResMut<RenderCapture>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if capture.captured == capture.requests {
+        return;
+    }
+    let Some(gpu_image) = gpu_images.get(capture.image) else {
+        return;
+    };
+    capture.captured = capture.requests;
+
+    let size = gpu_image.size;
+    let row_bytes = size.x as usize * 4;
+    // Rows copied out of a texture must be padded to a fixed alignment.
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("offscreen_capture_buffer"),
+        size: (padded_row_bytes * size.y as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("offscreen_capture"),
+    });
+    encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (mapped_sender, mapped_receiver) = channel();
+    render_device.map_buffer(&slice, MapMode::Read, move |result| {
+        let _ = mapped_sender.send(result);
+    });
+    render_device.poll(Maintain::Wait);
+    if !matches!(mapped_receiver.recv(), Ok(Ok(()))) {
+        log::error!("Cannot read back the offscreen frame");
+        return;
+    }
+
+    let mut data = Vec::with_capacity(row_bytes * size.y as usize);
+    for row in slice.get_mapped_range().chunks(padded_row_bytes) {
+        data.extend_from_slice(&row[..row_bytes]);
+    }
+    buffer.unmap();
+
+    let frame = Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        FORMAT,
+        RenderAssetUsages::default(),
+    );
+    let _ = capture.sender.send(frame);
+}
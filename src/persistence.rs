@@ -0,0 +1,75 @@
+//! Saves small amounts of player data between sessions.
+//!
+//! Each key is stored as a plain text file in the [`DataDir`]: the one [`AppPlugin`] was
+//! given, else `ATOMICCYCLES_DATA_DIR` if it is set, else the platform's data directory.
+//! Nothing is saved on the web.
+//!
+//! [`AppPlugin`]: crate::AppPlugin
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+/// Where player data is saved, or `None` if there is nowhere to save it.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DataDir(pub Option<PathBuf>);
+
+impl DataDir {
+    /// `dir` if given, otherwise the default data directory.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self(dir.or_else(default_data_dir))
+    }
+
+    /// The contents last saved under `key`, if any.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn load(&self, key: &str) -> Option<String> {
+        let path = self.0.as_ref()?.join(format!("{key}.txt"));
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Saves `contents` under `key`, replacing anything saved before.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn save(&self, key: &str, contents: &str) {
+        let Some(dir) = &self.0 else {
+            log::warn!("Cannot save {key}: no data directory");
+            return;
+        };
+        let result = std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(dir.join(format!("{key}.txt")), contents));
+        if let Err(error) = result {
+            log::warn!("Cannot save {key}: {error}");
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    pub fn load(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    #[cfg(target_family = "wasm")]
+    pub fn save(&self, _key: &str, _contents: &str) {}
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn default_data_dir() -> Option<PathBuf> {
+    use std::env::var_os;
+
+    if let Some(dir) = var_os("ATOMICCYCLES_DATA_DIR") {
+        return Some(dir.into());
+    }
+    let base: PathBuf = if cfg!(target_os = "windows") {
+        var_os("APPDATA")?.into()
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(var_os("HOME")?).join("Library/Application Support")
+    } else if let Some(dir) = var_os("XDG_DATA_HOME") {
+        dir.into()
+    } else {
+        PathBuf::from(var_os("HOME")?).join(".local/share")
+    };
+    Some(base.join("atomiccycles"))
+}
+
+#[cfg(target_family = "wasm")]
+fn default_data_dir() -> Option<PathBuf> {
+    None
+}
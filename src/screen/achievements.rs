@@ -0,0 +1,95 @@
+//! A gallery of every achievement that can be accessed from the title screen.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{
+    game::achievements::{Achievement, UnlockedAchievements, ACHIEVEMENTS},
+    ui::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Achievements), enter_achievements);
+
+    app.add_systems(
+        Update,
+        handle_achievements_action.run_if(in_state(Screen::Achievements)),
+    );
+    app.register_type::<AchievementsAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum AchievementsAction {
+    Back,
+}
+
+fn enter_achievements(mut commands: Commands, unlocked: Res<UnlockedAchievements>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Achievements))
+        .with_children(|children| {
+            children.header(format!(
+                "Achievements {}/{}",
+                unlocked.count(),
+                ACHIEVEMENTS.len()
+            ));
+            for achievement in ACHIEVEMENTS {
+                spawn_achievement(children, achievement, unlocked.contains(achievement));
+            }
+
+            children.button("Back").insert(AchievementsAction::Back);
+        });
+}
+
+fn spawn_achievement(parent: &mut ChildBuilder, achievement: &Achievement, unlocked: bool) {
+    let (name_color, description_color) = if unlocked {
+        (HEADER_TEXT, LABEL_TEXT)
+    } else {
+        (BUTTON_DISABLED_TEXT, BUTTON_DISABLED_TEXT)
+    };
+    parent
+        .spawn((
+            Name::new(achievement.name),
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(500.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section(
+                achievement.name,
+                TextStyle {
+                    font_size: 24.0,
+                    color: name_color,
+                    ..default()
+                },
+            ));
+            children.spawn(TextBundle::from_section(
+                achievement.description,
+                TextStyle {
+                    font_size: 20.0,
+                    color: description_color,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn handle_achievements_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&AchievementsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                AchievementsAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
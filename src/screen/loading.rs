@@ -1,42 +1,134 @@
 //! A loading screen during which game assets are loaded.
 //! This reduces stuttering, especially for audio on WASM.
+//!
+//! If an asset fails to load, or loading takes longer than [`LOADING_TIMEOUT_SECS`],
+//! [`Screen::LoadingFailed`] lists the assets that didn't load and offers to retry.
 
 use bevy::prelude::*;
 
 use super::Screen;
-use crate::{
-    game::assets::{HandleMap, ImageKey, SfxKey, SoundtrackKey},
-    ui::prelude::*,
-};
+use crate::{game::assets::AssetProgress, ui::prelude::*, AppSet};
+
+/// How long loading may take before giving up.
+pub const LOADING_TIMEOUT_SECS: f32 = 30.;
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LoadingTimeout>();
     app.add_systems(OnEnter(Screen::Loading), enter_loading);
     app.add_systems(
         Update,
-        continue_to_title.run_if(in_state(Screen::Loading).and_then(all_assets_loaded)),
+        (
+            tick_loading_timeout.in_set(AppSet::TickTimers),
+            update_loading.in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Loading)),
+    );
+
+    app.register_type::<LoadingFailedAction>();
+    app.add_systems(OnEnter(Screen::LoadingFailed), enter_loading_failed);
+    app.add_systems(
+        Update,
+        handle_loading_failed_action.run_if(in_state(Screen::LoadingFailed)),
     );
 }
 
-fn enter_loading(mut commands: Commands) {
+#[derive(Resource, Debug)]
+struct LoadingTimeout(Timer);
+
+impl Default for LoadingTimeout {
+    fn default() -> Self {
+        Self(Timer::from_seconds(LOADING_TIMEOUT_SECS, TimerMode::Once))
+    }
+}
+
+/// The label showing how many assets have loaded.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+struct LoadingProgressLabel;
+
+fn enter_loading(mut commands: Commands, mut timeout: ResMut<LoadingTimeout>) {
+    timeout.0.reset();
     commands
         .ui_root()
         .insert(StateScoped(Screen::Loading))
         .with_children(|children| {
             children.label("Loading...");
+            children.progress_bar();
+            children.label("").insert(LoadingProgressLabel);
         });
 }
 
-fn all_assets_loaded(
-    asset_server: Res<AssetServer>,
-    image_handles: Res<HandleMap<ImageKey>>,
-    sfx_handles: Res<HandleMap<SfxKey>>,
-    soundtrack_handles: Res<HandleMap<SoundtrackKey>>,
-) -> bool {
-    image_handles.all_loaded(&asset_server)
-        && sfx_handles.all_loaded(&asset_server)
-        && soundtrack_handles.all_loaded(&asset_server)
+fn tick_loading_timeout(time: Res<Time>, mut timeout: ResMut<LoadingTimeout>) {
+    timeout.0.tick(time.delta());
+}
+
+fn update_loading(
+    asset_progress: Res<AssetProgress>,
+    timeout: Res<LoadingTimeout>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut query_bar: Query<&mut ProgressBar>,
+    query_label: Query<&Children, With<LoadingProgressLabel>>,
+    mut query_text: Query<&mut Text>,
+) {
+    let progress = asset_progress.total();
+    for mut bar in &mut query_bar {
+        bar.0 = progress.fraction();
+    }
+    for children in &query_label {
+        let mut texts = query_text.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = format!("{} / {}", progress.loaded, progress.total);
+        }
+    }
+
+    if asset_progress.is_done() {
+        next_screen.set(Screen::Title);
+    } else if !progress.failed.is_empty() {
+        log::error!("Failed to load {}", progress.failed.join(", "));
+        next_screen.set(Screen::LoadingFailed);
+    } else if timeout.0.finished() {
+        log::error!("Timed out loading {}", progress.pending.join(", "));
+        next_screen.set(Screen::LoadingFailed);
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum LoadingFailedAction {
+    Retry,
 }
 
-fn continue_to_title(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+fn enter_loading_failed(mut commands: Commands, asset_progress: Res<AssetProgress>) {
+    let progress = asset_progress.total();
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::LoadingFailed))
+        .with_children(|children| {
+            children.header("Loading failed");
+            for path in &progress.failed {
+                children.label(format!("Could not load {path}"));
+            }
+            for path in &progress.pending {
+                children.label(format!("Timed out loading {path}"));
+            }
+            children.button("Retry").insert(LoadingFailedAction::Retry);
+        });
+}
+
+fn handle_loading_failed_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&LoadingFailedAction>,
+    mut asset_progress: ResMut<AssetProgress>,
+    asset_server: Res<AssetServer>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                LoadingFailedAction::Retry => {
+                    asset_progress.retry(&asset_server);
+                    next_screen.set(Screen::Loading);
+                }
+            }
+        }
+    }
 }
@@ -1,10 +1,13 @@
 //! The game's main screen states and transitions between them.
 
+mod achievements;
 mod credits;
 mod loading;
 mod playing;
+mod settings;
 mod splash;
 mod title;
+mod victory;
 
 use crate::ui::palette::BACKGROUND;
 use bevy::prelude::*;
@@ -12,14 +15,19 @@ use bevy::prelude::*;
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
     app.enable_state_scoped_entities::<Screen>();
+    app.add_computed_state::<InRun>();
+    app.enable_state_scoped_entities::<InRun>();
 
     app.insert_resource(ClearColor(BACKGROUND));
     app.add_plugins((
         splash::plugin,
         loading::plugin,
         title::plugin,
+        settings::plugin,
         credits::plugin,
+        achievements::plugin,
         playing::plugin,
+        victory::plugin,
     ));
 }
 
@@ -29,7 +37,28 @@ pub enum Screen {
     #[default]
     Splash,
     Loading,
+    /// Shown when assets failed to load, or took too long, with the option to retry.
+    LoadingFailed,
     Title,
+    Settings,
     Credits,
+    Achievements,
     Playing,
+    Victory,
+    /// Passed through when starting over from [`Screen::Victory`], so the old run ends first.
+    Restart,
+}
+
+/// Exists while a run is in progress, on both [`Screen::Playing`] and [`Screen::Victory`].
+/// Everything belonging to a run is scoped to this rather than [`Screen::Playing`],
+/// so the run can be continued after winning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InRun;
+
+impl ComputedStates for InRun {
+    type SourceStates = Screen;
+
+    fn compute(screen: Screen) -> Option<Self> {
+        matches!(screen, Screen::Playing | Screen::Victory).then_some(InRun)
+    }
 }
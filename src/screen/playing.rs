@@ -1,18 +1,21 @@
 //! The screen state for the main game loop.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
-use super::Screen;
-use crate::game::{audio::soundtrack::PlaySoundtrack, spawn::level::SpawnLevel};
+use super::{InRun, Screen};
+use crate::{
+    game::{audio::soundtrack::PlaySoundtrack, spawn::level::SpawnLevel},
+    input::{action_just_pressed, InputAction},
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Playing), enter_playing);
-    app.add_systems(OnExit(Screen::Playing), exit_playing);
+    app.add_systems(OnEnter(InRun), enter_playing);
+    app.add_systems(OnExit(InRun), exit_playing);
 
     app.add_systems(
         Update,
         return_to_title_screen
-            .run_if(in_state(Screen::Playing).and_then(input_just_pressed(KeyCode::Escape))),
+            .run_if(in_state(Screen::Playing).and_then(action_just_pressed(InputAction::Back))),
     );
 }
 
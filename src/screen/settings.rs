@@ -0,0 +1,360 @@
+//! A settings screen for rebinding controls that can be accessed from the title screen.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{
+    game::assets::{AssetManifest, ManifestHandle},
+    input::{action_just_pressed, ActionInput, BindingKind, InputAction, InputBindings},
+    settings::Settings,
+    ui::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    app.add_systems(OnEnter(Screen::Settings), enter_settings);
+    app.add_systems(OnExit(Screen::Settings), stop_rebinding);
+
+    app.add_systems(
+        Update,
+        (
+            return_to_title.run_if(action_just_pressed(InputAction::Back).and_then(not_rebinding)),
+            capture_binding,
+            handle_settings_action,
+            update_binding_texts,
+            update_asset_variant_text,
+            update_electron_trails_text,
+        )
+            .chain()
+            .run_if(in_state(Screen::Settings)),
+    );
+    app.register_type::<SettingsAction>();
+}
+
+const ACTION_LABEL_WIDTH: f32 = 220.;
+const BINDING_BUTTON_WIDTH: f32 = 200.;
+const BINDING_BUTTON_HEIGHT: f32 = 34.;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum SettingsAction {
+    /// Listen for a new input for an action.
+    Rebind(InputAction, BindingKind),
+    ResetBindings,
+    /// Switch to the asset manifest's next variant.
+    NextAssetVariant,
+    ToggleElectronTrails,
+    Back,
+}
+
+/// The action waiting for the player to press its new input.
+#[derive(Resource, Debug, Default)]
+struct Rebinding(Option<(InputAction, BindingKind)>);
+
+/// The text showing the inputs bound to an action.
+#[derive(Component)]
+struct BindingText(InputAction, BindingKind);
+
+#[derive(Component)]
+struct ConflictText;
+
+#[derive(Component)]
+struct AssetVariantText;
+
+#[derive(Component)]
+struct ElectronTrailsText;
+
+fn enter_settings(mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|children| {
+            children.header("Controls");
+            children
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|children| {
+                    for &action in InputAction::ALL {
+                        spawn_binding_row(children, action);
+                    }
+                });
+            children.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 18.,
+                        color: WARNING_TEXT,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+                ConflictText,
+            ));
+            spawn_small_button(
+                children,
+                "Reset to defaults",
+                SettingsAction::ResetBindings,
+                (),
+            );
+            spawn_small_button(
+                children,
+                "",
+                SettingsAction::NextAssetVariant,
+                AssetVariantText,
+            );
+            spawn_small_button(
+                children,
+                "",
+                SettingsAction::ToggleElectronTrails,
+                ElectronTrailsText,
+            );
+            children.button("Back").insert(SettingsAction::Back);
+        });
+}
+
+fn spawn_binding_row(parent: &mut ChildBuilder, action: InputAction) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    action.name(),
+                    TextStyle {
+                        font_size: 20.,
+                        color: LABEL_TEXT,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    width: Val::Px(ACTION_LABEL_WIDTH),
+                    ..default()
+                }),
+            );
+            for kind in [BindingKind::KeyboardMouse, BindingKind::Gamepad] {
+                spawn_small_button(
+                    parent,
+                    "",
+                    SettingsAction::Rebind(action, kind),
+                    BindingText(action, kind),
+                );
+            }
+        });
+}
+
+/// Spawns a button sized to fit in a binding row, with `text_marker` on its text.
+fn spawn_small_button(
+    parent: &mut ChildBuilder,
+    text: &str,
+    action: SettingsAction,
+    text_marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Name::new("Button"),
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(BINDING_BUTTON_WIDTH),
+                    height: Val::Px(BINDING_BUTTON_HEIGHT),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+            InteractionPalette {
+                none: NODE_BACKGROUND,
+                hovered: BUTTON_HOVERED_BACKGROUND,
+                pressed: BUTTON_PRESSED_BACKGROUND,
+            },
+            action,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Button Text"),
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 20.,
+                        color: BUTTON_TEXT,
+                        ..default()
+                    },
+                ),
+                text_marker,
+            ));
+        });
+}
+
+fn not_rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_none()
+}
+
+fn return_to_title(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn stop_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+/// Binds the first input pressed while an action is waiting for one.
+fn capture_binding(
+    // `ActionInput` reads the bindings too, so it can't be used alongside writing them.
+    mut input: ParamSet<(ActionInput, ResMut<InputBindings>)>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let Some((action, kind)) = rebinding.0 else {
+        return;
+    };
+    if let Some(binding) = input.p0().any_just_pressed(kind) {
+        log::info!("Bound {binding} to {}", action.name());
+        input.p1().rebind(action, binding);
+        rebinding.0 = None;
+    }
+}
+
+fn handle_settings_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    mut settings: ResMut<Settings>,
+    manifest: Res<ManifestHandle>,
+    manifests: Res<Assets<AssetManifest>>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match *action {
+                SettingsAction::Rebind(action, kind) => rebinding.0 = Some((action, kind)),
+                SettingsAction::ResetBindings => *bindings = InputBindings::default(),
+                SettingsAction::NextAssetVariant => {
+                    let Some(manifest) = manifests.get(&manifest.0) else {
+                        continue;
+                    };
+                    // Cycle through the manifest's own assets, then each variant.
+                    let ids: Vec<_> = manifest.variants().map(|(id, _)| id).collect();
+                    let next = match settings.asset_variant.as_deref() {
+                        None => ids.first(),
+                        Some(current) => ids.iter().skip_while(|id| **id != current).nth(1),
+                    };
+                    settings.asset_variant = next.map(|id| id.to_string());
+                }
+                SettingsAction::ToggleElectronTrails => {
+                    settings.electron_trails = !settings.electron_trails
+                }
+                SettingsAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
+
+fn update_binding_texts(
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut query_binding: Query<(&BindingText, &mut Text), Without<ConflictText>>,
+    mut query_conflict: Query<&mut Text, With<ConflictText>>,
+    query_added: Query<(), Added<BindingText>>,
+) {
+    if !bindings.is_changed() && !rebinding.is_changed() && query_added.is_empty() {
+        return;
+    }
+    let conflicts = bindings.conflicts();
+
+    for (BindingText(action, kind), mut text) in &mut query_binding {
+        let section = &mut text.sections[0];
+        if rebinding.0 == Some((*action, *kind)) {
+            section.value = "Press an input...".to_string();
+            section.style.color = HEADER_TEXT;
+            continue;
+        }
+        let bound: Vec<_> = bindings
+            .get(*action)
+            .iter()
+            .filter(|binding| binding.kind() == *kind)
+            .collect();
+        section.value = if bound.is_empty() {
+            "-".to_string()
+        } else {
+            bound
+                .iter()
+                .map(|binding| binding.to_string())
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+        let conflicted = bound.iter().any(|binding| {
+            conflicts
+                .iter()
+                .any(|conflict| conflict.binding == **binding)
+        });
+        section.style.color = if conflicted {
+            WARNING_TEXT
+        } else {
+            BUTTON_TEXT
+        };
+    }
+
+    for mut text in &mut query_conflict {
+        text.sections[0].value = conflicts
+            .iter()
+            .map(|conflict| {
+                let actions: Vec<_> = conflict
+                    .actions
+                    .iter()
+                    .map(|action| action.name())
+                    .collect();
+                format!("{} is bound to {}", conflict.binding, actions.join(" and "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+}
+
+fn update_asset_variant_text(
+    settings: Res<Settings>,
+    manifest: Res<ManifestHandle>,
+    manifests: Res<Assets<AssetManifest>>,
+    mut query_text: Query<&mut Text, With<AssetVariantText>>,
+) {
+    let name = manifests
+        .get(&manifest.0)
+        .and_then(|manifest| {
+            let current = settings.asset_variant.as_deref()?;
+            manifest.variants().find(|(id, _)| *id == current)
+        })
+        .map_or("Default", |(_, name)| name);
+    for mut text in &mut query_text {
+        let value = format!("Sprites: {name}");
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn update_electron_trails_text(
+    settings: Res<Settings>,
+    mut query_text: Query<&mut Text, With<ElectronTrailsText>>,
+) {
+    let value = if settings.electron_trails {
+        "Trails: On"
+    } else {
+        "Trails: Off"
+    };
+    for mut text in &mut query_text {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+        }
+    }
+}
@@ -6,6 +6,7 @@ use bevy::{
 };
 
 use super::Screen;
+use crate::game::assets::asset_path;
 use crate::ui::palette::BACKGROUND;
 use crate::{ui::prelude::*, AppSet};
 
@@ -61,7 +62,7 @@ fn spawn_splash(mut commands: Commands, asset_server: Res<AssetServer>) {
                     image: UiImage::new(asset_server.load_with_settings(
                         // This should be an embedded asset for instant loading, but that is
                         // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
-                        "images/splash.png",
+                        asset_path("images/splash.png"),
                         |settings: &mut ImageLoaderSettings| {
                             // Make an exception for the splash image in case
                             // `ImagePlugin::default_nearest()` is used for pixel art.
@@ -1,6 +1,7 @@
 //! The title screen that appears when the game starts.
 
 use super::Screen;
+use crate::game::assets::asset_path;
 use crate::ui::prelude::*;
 use bevy::prelude::*;
 use bevy::render::texture::{ImageLoaderSettings, ImageSampler};
@@ -16,6 +17,8 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 enum TitleAction {
     Play,
+    Achievements,
+    Settings,
     Credits,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
@@ -28,6 +31,10 @@ fn enter_title(mut commands: Commands, asset_server: Res<AssetServer>) {
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
             children.button("Play").insert(TitleAction::Play);
+            children
+                .button("Trophies")
+                .insert(TitleAction::Achievements);
+            children.button("Settings").insert(TitleAction::Settings);
             children.button("Credits").insert(TitleAction::Credits);
 
             #[cfg(not(target_family = "wasm"))]
@@ -45,7 +52,7 @@ fn enter_title(mut commands: Commands, asset_server: Res<AssetServer>) {
             image: UiImage::new(asset_server.load_with_settings(
                 // This should be an embedded asset for instant loading, but that is
                 // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
-                "images/title_image_no_middle.png",
+                asset_path("images/title_image_no_middle.png"),
                 |settings: &mut ImageLoaderSettings| {
                     // Make an exception for the splash image in case
                     // `ImagePlugin::default_nearest()` is used for pixel art.
@@ -66,6 +73,8 @@ fn handle_title_action(
         if matches!(interaction, Interaction::Pressed) {
             match action {
                 TitleAction::Play => next_screen.set(Screen::Playing),
+                TitleAction::Achievements => next_screen.set(Screen::Achievements),
+                TitleAction::Settings => next_screen.set(Screen::Settings),
                 TitleAction::Credits => next_screen.set(Screen::Credits),
 
                 #[cfg(not(target_family = "wasm"))]
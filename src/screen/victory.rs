@@ -0,0 +1,79 @@
+//! The end-of-run summary, shown over the atom once the run has been won.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{
+    game::{elements::element_name, victory::RunOutcome},
+    ui::{palette::NODE_BACKGROUND, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Victory), (enter_victory, pause_run));
+    app.add_systems(OnExit(Screen::Victory), resume_run);
+    app.add_systems(OnEnter(Screen::Restart), restart_run);
+
+    app.add_systems(
+        Update,
+        handle_victory_action.run_if(in_state(Screen::Victory)),
+    );
+    app.register_type::<VictoryAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum VictoryAction {
+    /// Keep playing the same run in endless mode.
+    Continue,
+    StartOver,
+}
+
+fn enter_victory(mut commands: Commands, outcome: Res<RunOutcome>) {
+    commands
+        .ui_root()
+        .insert((
+            StateScoped(Screen::Victory),
+            BackgroundColor(NODE_BACKGROUND.with_alpha(0.8)),
+        ))
+        .with_children(|children| {
+            children.header("Victory!");
+            if let Some(summary) = &outcome.summary {
+                let duration = summary.duration as u32;
+                children.label(format!("Time: {}:{:02}", duration / 60, duration % 60));
+                children.label(format!("Cycles earned: {}", summary.total_cycles));
+                children.label(format!("Purchases: {}", summary.purchases));
+                children.label(format!("Element: {}", element_name(summary.element)));
+            }
+
+            children.button("Continue").insert(VictoryAction::Continue);
+            children
+                .button("Start over")
+                .insert(VictoryAction::StartOver);
+        });
+}
+
+fn pause_run(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_run(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn restart_run(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Playing);
+}
+
+fn handle_victory_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&VictoryAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                VictoryAction::Continue => next_screen.set(Screen::Playing),
+                VictoryAction::StartOver => next_screen.set(Screen::Restart),
+            }
+        }
+    }
+}
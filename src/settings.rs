@@ -0,0 +1,76 @@
+//! Player preferences, saved between sessions.
+
+use bevy::prelude::*;
+
+use crate::persistence::DataDir;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    let settings = Settings::load(app.world().resource::<DataDir>());
+    app.insert_resource(settings);
+    app.add_systems(
+        Update,
+        save_settings
+            .run_if(resource_changed::<Settings>.and_then(not(resource_added::<Settings>))),
+    );
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct Settings {
+    /// Set once the tutorial has been finished or skipped.
+    pub tutorial_complete: bool,
+    /// The asset manifest variant in use, or `None` for the manifest's own assets.
+    pub asset_variant: Option<String>,
+    /// Whether electrons leave a trail behind them as they revolve.
+    pub electron_trails: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tutorial_complete: false,
+            asset_variant: None,
+            electron_trails: true,
+        }
+    }
+}
+
+impl Settings {
+    const SAVE_KEY: &'static str = "settings";
+
+    /// Loads the saved settings, keeping the defaults for anything missing or unreadable.
+    fn load(data_dir: &DataDir) -> Self {
+        let mut settings = Self::default();
+        let saved = data_dir.load(Self::SAVE_KEY).unwrap_or_default();
+        for (key, value) in saved.lines().filter_map(|line| line.split_once('=')) {
+            match (key.trim(), value.trim()) {
+                ("tutorial_complete", value) => {
+                    settings.tutorial_complete = value.parse().unwrap_or_default()
+                }
+                ("asset_variant", value) => {
+                    settings.asset_variant = Some(value.to_string()).filter(|v| !v.is_empty())
+                }
+                ("electron_trails", value) => {
+                    settings.electron_trails = value.parse().unwrap_or(true)
+                }
+                (key, _) => log::warn!("Ignoring unknown setting {key}"),
+            }
+        }
+        settings
+    }
+
+    fn save(&self, data_dir: &DataDir) {
+        let contents = format!(
+            "tutorial_complete={}\nasset_variant={}\nelectron_trails={}\n",
+            self.tutorial_complete,
+            self.asset_variant.as_deref().unwrap_or_default(),
+            self.electron_trails
+        );
+        data_dir.save(Self::SAVE_KEY, &contents);
+    }
+}
+
+fn save_settings(settings: Res<Settings>, data_dir: Res<DataDir>) {
+    settings.save(&data_dir);
+}
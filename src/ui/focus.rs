@@ -0,0 +1,211 @@
+//! Keyboard and gamepad navigation between buttons.
+//!
+//! The focus moves between visible [`Button`]s with the focus [`InputAction`]s, and
+//! [`InputAction::Activate`] presses the focused button by setting its [`Interaction`], so
+//! widgets respond to it exactly as they do to a mouse click.
+
+use bevy::{prelude::*, ui::UiSystem};
+
+use super::palette::HEADER_TEXT;
+use crate::{
+    input::{ActionInput, InputAction},
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<UiFocus>();
+    app.add_systems(Startup, spawn_focus_ring);
+    app.add_systems(PreUpdate, activate_focused.after(UiSystem::Focus));
+    app.add_systems(
+        Update,
+        (
+            (clear_focus_on_click, move_focus)
+                .chain()
+                .in_set(AppSet::RecordInput),
+            update_focus_ring.in_set(AppSet::Update),
+        ),
+    );
+    app.add_systems(Last, release_activated);
+}
+
+const FOCUS_RING_WIDTH: f32 = 3.;
+/// Gap between the focus ring and the focused button.
+const FOCUS_RING_OFFSET: f32 = 2.;
+/// How much sideways distance counts against a button when moving focus, compared to
+/// distance in the direction of movement.
+const SIDEWAYS_WEIGHT: f32 = 2.;
+
+/// The button navigated to with the keyboard or a gamepad.
+#[derive(Resource, Debug, Default)]
+pub struct UiFocus {
+    focused: Option<Entity>,
+    /// The button pressed this frame by [`InputAction::Activate`].
+    activated: Option<Entity>,
+}
+
+impl UiFocus {
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused
+    }
+}
+
+#[derive(Component)]
+struct FocusRing;
+
+fn spawn_focus_ring(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Focus Ring"),
+        NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(FOCUS_RING_WIDTH)),
+                ..default()
+            },
+            border_color: BorderColor(HEADER_TEXT),
+            z_index: ZIndex::Global(90),
+            ..default()
+        },
+        FocusRing,
+    ));
+}
+
+type FocusableQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Node,
+        &'static GlobalTransform,
+        &'static InheritedVisibility,
+        Option<&'static CalculatedClip>,
+    ),
+    With<Button>,
+>;
+
+/// The centre and size, in logical pixels, of every button that can take focus.
+fn focusable_buttons<'a>(
+    query_button: &'a FocusableQuery,
+) -> impl Iterator<Item = (Entity, Vec2, Vec2)> + 'a {
+    query_button
+        .iter()
+        .filter(|(_, node, _, visibility, _)| visibility.get() && node.size() != Vec2::ZERO)
+        .map(|(entity, node, transform, _, clip)| {
+            (
+                entity,
+                transform.translation().truncate(),
+                node.size(),
+                clip,
+            )
+        })
+        .filter(|(_, center, _, clip)| clip.is_none_or(|clip| clip.clip.contains(*center)))
+        .map(|(entity, center, size, _)| (entity, center, size))
+}
+
+fn clear_focus_on_click(mut focus: ResMut<UiFocus>, mouse: Res<ButtonInput<MouseButton>>) {
+    if mouse.just_pressed(MouseButton::Left) && focus.focused.is_some() {
+        focus.focused = None;
+    }
+}
+
+fn move_focus(input: ActionInput, mut focus: ResMut<UiFocus>, query_button: FocusableQuery) {
+    let current = focus.focused.and_then(|focused| {
+        focusable_buttons(&query_button).find(|(entity, ..)| *entity == focused)
+    });
+    if current.is_none() && focus.focused.is_some() {
+        focus.focused = None;
+    }
+
+    // UI coordinates grow downwards.
+    let direction = [
+        (InputAction::FocusUp, Vec2::NEG_Y),
+        (InputAction::FocusDown, Vec2::Y),
+        (InputAction::FocusLeft, Vec2::NEG_X),
+        (InputAction::FocusRight, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(action, _)| input.just_pressed(*action))
+    .map(|(_, direction)| direction);
+    let Some(direction) = direction else {
+        return;
+    };
+
+    let next = match current {
+        // Start from the top left button.
+        None => focusable_buttons(&query_button)
+            .min_by(|(_, a, _), (_, b, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+            .map(|(entity, ..)| entity),
+        Some((current, from, _)) => focusable_buttons(&query_button)
+            .filter(|(entity, ..)| *entity != current)
+            .filter_map(|(entity, center, _)| {
+                let offset = center - from;
+                let along = offset.dot(direction);
+                let sideways = offset.perp_dot(direction).abs();
+                (along > 0.).then_some((entity, along + sideways * SIDEWAYS_WEIGHT))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity),
+    };
+    if let Some(next) = next {
+        focus.focused = Some(next);
+    }
+}
+
+fn update_focus_ring(
+    focus: Res<UiFocus>,
+    query_node: Query<(&Node, &GlobalTransform), Without<FocusRing>>,
+    mut query_ring: Query<&mut Style, With<FocusRing>>,
+) {
+    let Ok(mut style) = query_ring.get_single_mut() else {
+        return;
+    };
+    let Some((node, transform)) = focus.focused.and_then(|entity| query_node.get(entity).ok())
+    else {
+        if style.display != Display::None {
+            style.display = Display::None;
+        }
+        return;
+    };
+
+    let inset = FOCUS_RING_WIDTH + FOCUS_RING_OFFSET;
+    let size = node.size() + 2. * inset;
+    let corner = transform.translation().truncate() - size / 2.;
+    style.display = Display::Flex;
+    style.left = Val::Px(corner.x);
+    style.top = Val::Px(corner.y);
+    style.width = Val::Px(size.x);
+    style.height = Val::Px(size.y);
+}
+
+/// Presses the focused button, after Bevy has updated interactions from the mouse.
+fn activate_focused(
+    input: ActionInput,
+    mut focus: ResMut<UiFocus>,
+    mut query_interaction: Query<&mut Interaction, With<Button>>,
+) {
+    if !input.just_pressed(InputAction::Activate) {
+        return;
+    }
+    let Some(focused) = focus.focused else {
+        return;
+    };
+    if let Ok(mut interaction) = query_interaction.get_mut(focused) {
+        *interaction = Interaction::Pressed;
+        focus.activated = Some(focused);
+    }
+}
+
+/// Lets go of a button pressed by [`activate_focused`] once every system has seen the press.
+fn release_activated(
+    mut focus: ResMut<UiFocus>,
+    mut query_interaction: Query<&mut Interaction, With<Button>>,
+) {
+    let Some(activated) = focus.activated.take() else {
+        return;
+    };
+    if let Ok(mut interaction) = query_interaction.get_mut(activated) {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+    }
+}
@@ -1,10 +1,24 @@
 use bevy::prelude::*;
 
-use crate::game::{assets::SfxKey, audio::sfx::PlaySfx};
+use crate::{
+    game::{assets::SfxKey, audio::sfx::PlaySfx},
+    AppSet,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
-    app.add_systems(Update, (apply_interaction_palette, trigger_interaction_sfx));
+    app.register_type::<InteractionDisabled>();
+    app.register_type::<DeniedShake>();
+    app.add_systems(
+        Update,
+        (
+            apply_interaction_palette,
+            trigger_interaction_sfx,
+            (tick_denied_shake, apply_denied_shake)
+                .chain()
+                .in_set(AppSet::Update),
+        ),
+    );
 }
 
 pub type InteractionQuery<'w, 's, T> =
@@ -19,6 +33,27 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
+/// Marks a widget that can't currently be activated.
+/// Pressing it plays a denied sound and shakes the widget instead.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct InteractionDisabled;
+
+const DENIED_SHAKE_SECS: f32 = 0.3;
+const DENIED_SHAKE_DISTANCE: f32 = 4.;
+const DENIED_SHAKE_FREQUENCY: f32 = 30.;
+
+/// Shakes a widget side to side after it was pressed while disabled.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct DeniedShake(Timer);
+
+impl Default for DeniedShake {
+    fn default() -> Self {
+        Self(Timer::from_seconds(DENIED_SHAKE_SECS, TimerMode::Once))
+    }
+}
+
 fn apply_interaction_palette(
     mut palette_query: InteractionQuery<(&InteractionPalette, &mut BackgroundColor)>,
 ) {
@@ -33,14 +68,41 @@ fn apply_interaction_palette(
 }
 
 fn trigger_interaction_sfx(
-    mut interactions: Query<&Interaction, Changed<Interaction>>,
+    mut interactions: InteractionQuery<(Entity, Has<InteractionDisabled>)>,
     mut commands: Commands,
 ) {
-    for interaction in &mut interactions {
-        match interaction {
-            Interaction::Hovered => commands.trigger(PlaySfx::Key(SfxKey::ButtonHover)),
-            Interaction::Pressed => commands.trigger(PlaySfx::Key(SfxKey::ButtonPress)),
+    for (interaction, (entity, disabled)) in &mut interactions {
+        match (interaction, disabled) {
+            (Interaction::Hovered, false) => commands.trigger(PlaySfx::Key(SfxKey::ButtonHover)),
+            (Interaction::Pressed, false) => commands.trigger(PlaySfx::Key(SfxKey::ButtonPress)),
+            (Interaction::Pressed, true) => {
+                commands.trigger(PlaySfx::Denied);
+                commands.entity(entity).insert(DeniedShake::default());
+            }
             _ => (),
         }
     }
 }
+
+fn tick_denied_shake(time: Res<Time>, mut shake_query: Query<&mut DeniedShake>) {
+    for mut shake in &mut shake_query {
+        shake.0.tick(time.delta());
+    }
+}
+
+fn apply_denied_shake(
+    mut commands: Commands,
+    mut shake_query: Query<(Entity, &DeniedShake, &mut Style)>,
+) {
+    for (entity, shake, mut style) in &mut shake_query {
+        if shake.0.finished() {
+            style.left = Val::Auto;
+            commands.entity(entity).remove::<DeniedShake>();
+            continue;
+        }
+        let elapsed = shake.0.elapsed_secs();
+        let falloff = 1. - shake.0.fraction();
+        let offset = (elapsed * DENIED_SHAKE_FREQUENCY).sin() * DENIED_SHAKE_DISTANCE * falloff;
+        style.left = Val::Px(offset);
+    }
+}
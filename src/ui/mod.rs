@@ -3,14 +3,19 @@
 // Unused utilities and re-exports may trigger these lints undesirably.
 #![allow(dead_code, unused_imports)]
 
+pub mod focus;
 pub mod interaction;
 pub mod palette;
+pub mod progress_bar;
+pub mod toast;
 mod widgets;
 
 pub mod prelude {
     pub use super::{
-        interaction::{InteractionPalette, InteractionQuery},
+        interaction::{InteractionDisabled, InteractionPalette, InteractionQuery},
         palette as ui_palette,
+        progress_bar::ProgressBar,
+        toast::{Toast, ToastPriority},
         widgets::{Containers as _, Widgets as _},
     };
 }
@@ -18,5 +23,10 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        focus::plugin,
+        interaction::plugin,
+        progress_bar::plugin,
+        toast::plugin,
+    ));
 }
@@ -2,10 +2,14 @@ use bevy::prelude::*;
 
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.404, 0.157, 0.357); // #67285B
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.345, 0.133, 0.306); // #58224E
+pub const BUTTON_DISABLED_BACKGROUND: Color = Color::srgb(0.239, 0.094, 0.212); // #3D1836
+pub const BUTTON_MAXED_BACKGROUND: Color = Color::srgb(0.157, 0.4, 0.431); // #28666E
 
 pub const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
+pub const BUTTON_DISABLED_TEXT: Color = Color::srgb(0.6, 0.6, 0.6);
 pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 pub const HEADER_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
+pub const WARNING_TEXT: Color = Color::srgb(0.937, 0.416, 0.357); // #EF6A5B
 
 pub const NODE_BACKGROUND: Color = Color::srgb(0.478, 0.188, 0.424); // #7A306C
 
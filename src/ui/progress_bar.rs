@@ -0,0 +1,32 @@
+//! A bar that fills up as something progresses.
+//!
+//! Spawn one with `Widgets::progress_bar` and set its [`ProgressBar`].
+
+use bevy::{prelude::*, ui::Val::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ProgressBar>();
+    app.add_systems(Update, fill_progress_bars);
+}
+
+/// How full a progress bar is, from 0 to 1.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ProgressBar(pub f32);
+
+/// The part of a [`ProgressBar`] that fills up.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ProgressBarFill;
+
+fn fill_progress_bars(
+    query_bar: Query<(&ProgressBar, &Children), Changed<ProgressBar>>,
+    mut query_fill: Query<&mut Style, With<ProgressBarFill>>,
+) {
+    for (bar, children) in &query_bar {
+        let mut fills = query_fill.iter_many_mut(children);
+        while let Some(mut style) = fills.fetch_next() {
+            style.width = Percent(bar.0.clamp(0., 1.) * 100.);
+        }
+    }
+}
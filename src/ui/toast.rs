@@ -0,0 +1,304 @@
+//! Short notifications that stack at the top of the screen.
+//!
+//! Trigger a [`Toast`] from anywhere to queue it. Toasts are shown highest [`ToastPriority`]
+//! first, slide in, and dismiss themselves once their duration has passed.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::palette::*;
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ToastQueue>();
+    app.add_systems(Startup, spawn_toast_container);
+    app.observe(queue_toast);
+    app.add_systems(
+        Update,
+        (
+            tick_toasts.in_set(AppSet::TickTimers),
+            (show_queued_toasts, animate_toasts, dismiss_toasts)
+                .chain()
+                .in_set(AppSet::Update),
+        ),
+    );
+}
+
+/// How many toasts are on screen at once, the rest wait in the queue.
+const MAX_VISIBLE_TOASTS: usize = 3;
+const TOAST_WIDTH: f32 = 360.;
+const ICON_SIZE: f32 = 32.;
+/// How long a toast takes to slide in or out.
+const SLIDE_SECS: f32 = 0.25;
+
+/// Trigger this event to show a notification.
+#[derive(Event, Debug, Clone)]
+pub struct Toast {
+    pub title: String,
+    pub body: String,
+    pub icon: Option<Handle<Image>>,
+    /// The frame of `icon` to show, when it is a sprite sheet.
+    pub icon_atlas: Option<TextureAtlas>,
+    pub priority: ToastPriority,
+    /// How long the toast stays on screen, not counting its slide animations.
+    pub duration: Duration,
+    /// The toast is only shown on this screen, or on any screen if `None`.
+    pub scope: Option<Screen>,
+}
+
+impl Toast {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: String::new(),
+            icon: None,
+            icon_atlas: None,
+            priority: ToastPriority::default(),
+            duration: Duration::from_secs(4),
+            scope: None,
+        }
+    }
+
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Show a single frame of the icon's sprite sheet rather than the whole sheet.
+    pub fn with_icon_atlas(mut self, atlas: TextureAtlas) -> Self {
+        self.icon_atlas = Some(atlas);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: ToastPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Only show the toast on `screen`, dropping it when the screen is left.
+    pub fn scoped(mut self, screen: Screen) -> Self {
+        self.scope = Some(screen);
+        self
+    }
+
+    fn is_same(&self, other: &Toast) -> bool {
+        self.title == other.title && self.body == other.body
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Reflect)]
+pub enum ToastPriority {
+    Low,
+    #[default]
+    Normal,
+    /// Shown before any other queued toast, with a highlighted border.
+    High,
+}
+
+impl ToastPriority {
+    fn border_color(&self) -> Color {
+        match self {
+            ToastPriority::Low | ToastPriority::Normal => NODE_BACKGROUND,
+            ToastPriority::High => HEADER_TEXT,
+        }
+    }
+}
+
+/// Toasts waiting for room on screen.
+#[derive(Resource, Debug, Default)]
+struct ToastQueue(Vec<Toast>);
+
+impl ToastQueue {
+    /// Takes the oldest toast with the highest priority.
+    fn pop(&mut self) -> Option<Toast> {
+        let priority = self.0.iter().map(|toast| toast.priority).max()?;
+        let index = self.0.iter().position(|toast| toast.priority == priority)?;
+        Some(self.0.remove(index))
+    }
+}
+
+#[derive(Component)]
+struct ToastContainer;
+
+/// A toast on screen.
+#[derive(Component, Debug)]
+struct ActiveToast {
+    toast: Toast,
+    /// Seconds since the toast was shown.
+    age: f32,
+}
+
+impl ActiveToast {
+    fn lifetime(&self) -> f32 {
+        self.toast.duration.as_secs_f32() + 2. * SLIDE_SECS
+    }
+
+    /// How far the toast has slid onto the screen, from 0 to 1.
+    fn visibility(&self) -> f32 {
+        let remaining = self.lifetime() - self.age;
+        let t = (self.age.min(remaining) / SLIDE_SECS).clamp(0., 1.);
+        // Ease out.
+        1. - (1. - t).powi(2)
+    }
+}
+
+fn spawn_toast_container(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Toasts"),
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                top: Val::Px(5.0),
+                width: Val::Percent(100.),
+                row_gap: Val::Px(5.),
+                ..default()
+            },
+            z_index: ZIndex::Global(100),
+            ..default()
+        },
+        ToastContainer,
+    ));
+}
+
+fn queue_toast(
+    trigger: Trigger<Toast>,
+    mut queue: ResMut<ToastQueue>,
+    mut query_active: Query<&mut ActiveToast>,
+) {
+    let toast = trigger.event();
+
+    // Repeats of a toast keep the one already shown on screen instead of stacking up.
+    for mut active in &mut query_active {
+        if active.toast.is_same(toast) {
+            active.age = active.age.min(SLIDE_SECS);
+            return;
+        }
+    }
+    if queue.0.iter().any(|queued| queued.is_same(toast)) {
+        return;
+    }
+    queue.0.push(toast.clone());
+}
+
+fn show_queued_toasts(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    screen: Option<Res<State<Screen>>>,
+    query_container: Query<Entity, With<ToastContainer>>,
+    query_active: Query<(), With<ActiveToast>>,
+) {
+    let Ok(container) = query_container.get_single() else {
+        return;
+    };
+    let mut visible = query_active.iter().count();
+    while visible < MAX_VISIBLE_TOASTS {
+        let Some(toast) = queue.pop() else {
+            break;
+        };
+        if toast
+            .scope
+            .as_ref()
+            .is_some_and(|scope| screen.as_deref().map(State::get) != Some(scope))
+        {
+            continue;
+        }
+        commands.entity(container).with_children(|parent| {
+            spawn_toast(parent, toast);
+        });
+        visible += 1;
+    }
+}
+
+fn spawn_toast(parent: &mut ChildBuilder, toast: Toast) {
+    let mut entity = parent.spawn((
+        Name::new(format!("Toast: {}", toast.title)),
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.),
+                width: Val::Px(TOAST_WIDTH),
+                padding: UiRect::all(Val::Px(8.)),
+                border: UiRect::all(Val::Px(2.)),
+                left: Val::Px(TOAST_WIDTH),
+                ..default()
+            },
+            background_color: BackgroundColor(NODE_BACKGROUND),
+            border_color: BorderColor(toast.priority.border_color()),
+            ..default()
+        },
+    ));
+    if let Some(scope) = toast.scope.clone() {
+        entity.insert(StateScoped(scope));
+    }
+    entity.with_children(|parent| {
+        if let Some(icon) = toast.icon.clone() {
+            let mut icon_entity = parent.spawn(ImageBundle {
+                style: Style {
+                    width: Val::Px(ICON_SIZE),
+                    height: Val::Px(ICON_SIZE),
+                    flex_shrink: 0.,
+                    ..default()
+                },
+                image: UiImage::new(icon),
+                ..default()
+            });
+            if let Some(atlas) = toast.icon_atlas.clone() {
+                icon_entity.insert(atlas);
+            }
+        }
+        let mut sections = vec![TextSection::new(
+            toast.title.clone(),
+            TextStyle {
+                font_size: 22.,
+                color: HEADER_TEXT,
+                ..default()
+            },
+        )];
+        if !toast.body.is_empty() {
+            sections.push(TextSection::new(
+                format!("\n{}", toast.body),
+                TextStyle {
+                    font_size: 18.,
+                    color: LABEL_TEXT,
+                    ..default()
+                },
+            ));
+        }
+        parent.spawn(TextBundle::from_sections(sections));
+    });
+    entity.insert(ActiveToast { toast, age: 0. });
+}
+
+fn tick_toasts(time: Res<Time>, mut query: Query<&mut ActiveToast>) {
+    for mut active in &mut query {
+        active.age += time.delta_seconds();
+    }
+}
+
+fn animate_toasts(mut query: Query<(&ActiveToast, &mut Style)>) {
+    for (active, mut style) in &mut query {
+        style.left = Val::Px(TOAST_WIDTH * (1. - active.visibility()));
+    }
+}
+
+fn dismiss_toasts(mut commands: Commands, query: Query<(Entity, &ActiveToast)>) {
+    for (entity, active) in &query {
+        if active.age >= active.lifetime() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
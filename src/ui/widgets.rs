@@ -2,7 +2,11 @@
 
 use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
 
-use super::{interaction::InteractionPalette, palette::*};
+use super::{
+    interaction::InteractionPalette,
+    palette::*,
+    progress_bar::{ProgressBar, ProgressBarFill},
+};
 
 /// An extension trait for spawning UI widgets.
 pub trait Widgets {
@@ -14,6 +18,9 @@ pub trait Widgets {
 
     /// Spawn a simple text label.
     fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+
+    /// Spawn an empty [`ProgressBar`].
+    fn progress_bar(&mut self) -> EntityCommands<'_>;
 }
 
 impl<T: Spawn> Widgets for T {
@@ -112,6 +119,39 @@ impl<T: Spawn> Widgets for T {
         });
         entity
     }
+
+    fn progress_bar(&mut self) -> EntityCommands<'_> {
+        let mut entity = self.spawn((
+            Name::new("Progress Bar"),
+            ProgressBar::default(),
+            NodeBundle {
+                style: Style {
+                    width: Px(500.0),
+                    height: Px(24.0),
+                    padding: UiRect::all(Px(4.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Progress Bar Fill"),
+                ProgressBarFill,
+                NodeBundle {
+                    style: Style {
+                        width: Px(0.0),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(LABEL_TEXT),
+                    ..default()
+                },
+            ));
+        });
+        entity
+    }
 }
 
 /// An extension trait for spawning UI containers.
@@ -0,0 +1,47 @@
+//! Unlocking achievements, whichever way their goal is reached.
+
+mod common;
+
+use atomiccycles::game::{
+    achievements::{AchievementProgress, UnlockedAchievements, ACHIEVEMENTS},
+    upgrades::{costs::compute_electron_cost, BuyElectron, BuyNextRing, Upgrade},
+};
+use common::TestGame;
+
+fn first_ring_filled(game: &mut TestGame) -> bool {
+    let world = game.app.world();
+    let achievement = ACHIEVEMENTS
+        .iter()
+        .find(|achievement| achievement.id == "fill_first_ring")
+        .unwrap();
+    world
+        .resource::<AchievementProgress>()
+        .filled_rings
+        .contains(&0)
+        && world
+            .resource::<UnlockedAchievements>()
+            .contains(achievement)
+}
+
+#[test]
+fn buying_electrons_fills_a_ring() {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+    assert!(!first_ring_filled(&mut game));
+
+    game.set_cycles(compute_electron_cost(0, 1));
+    game.press(BuyElectron, Some(0));
+    assert!(first_ring_filled(&mut game));
+}
+
+#[test]
+fn electrons_added_without_buying_fill_a_ring() {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    // As the console's `electron fill` does.
+    let ring = game.ring(0);
+    BuyElectron.apply(game.app.world_mut(), ring, 2);
+    game.update();
+    assert!(first_ring_filled(&mut game));
+}
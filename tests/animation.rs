@@ -0,0 +1,116 @@
+//! Particles animated from their sprite sheet.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::{
+    game::{
+        animation::{AnimationState, SpriteAnimation},
+        spawn::atom::{Electron, Proton},
+        upgrades::{BuyElectron, BuyNextRing, SpeedUpgrade},
+    },
+    settings::Settings,
+};
+use bevy::prelude::*;
+use common::TestGame;
+
+fn started_game() -> TestGame {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+    game
+}
+
+/// The animation state and atlas index of the first `T`.
+fn sprite<T: Component>(game: &mut TestGame) -> (AnimationState, usize) {
+    let world = game.app.world_mut();
+    let (animation, atlas) = world
+        .query_filtered::<(&SpriteAnimation, &TextureAtlas), With<T>>()
+        .iter(world)
+        .next()
+        .expect("the particle is animated");
+    (animation.state(), atlas.index)
+}
+
+#[test]
+fn idle_electrons_shimmer() {
+    let mut game = started_game();
+    let (state, first) = sprite::<Electron>(&mut game);
+    assert_eq!(state, AnimationState::Idle);
+
+    game.advance(Duration::from_millis(500));
+    let (state, index) = sprite::<Electron>(&mut game);
+    assert_eq!(state, AnimationState::Idle);
+    assert_ne!(index, first);
+}
+
+#[test]
+fn cycling_electrons_are_excited() {
+    let mut game = started_game();
+    let (_, idle) = sprite::<Electron>(&mut game);
+
+    game.tap(KeyCode::Space);
+    let (state, excited) = sprite::<Electron>(&mut game);
+    assert_eq!(state, AnimationState::Excited);
+    assert_ne!(excited / 8, idle / 8, "excited frames are on their own row");
+
+    // Settles down once the revolution is over.
+    game.advance(Duration::from_secs(10));
+    assert_eq!(sprite::<Electron>(&mut game).0, AnimationState::Idle);
+}
+
+#[test]
+fn nucleons_spin() {
+    let mut game = started_game();
+    let (_, first) = sprite::<Proton>(&mut game);
+    game.advance(Duration::from_millis(500));
+    assert_ne!(sprite::<Proton>(&mut game).1, first);
+}
+
+#[test]
+fn toast_icons_show_a_single_frame() {
+    let mut game = started_game();
+    game.set_cycles(0);
+    game.press(SpeedUpgrade, Some(0));
+    game.update();
+
+    let world = game.app.world_mut();
+    let atlas = world
+        .query_filtered::<&TextureAtlas, (With<UiImage>, With<Node>)>()
+        .iter(world)
+        .next()
+        .expect("the failed purchase toast has an icon from the sprite sheet");
+    assert!(atlas.index < 8 * 4);
+}
+
+#[test]
+fn switching_variant_restyles_existing_particles() {
+    let mut game = started_game();
+    let set_variant = |game: &mut TestGame, variant: Option<&str>| {
+        game.app
+            .world_mut()
+            .resource_mut::<Settings>()
+            .asset_variant = variant.map(str::to_string);
+        game.update();
+        game.update();
+    };
+    let animated = |game: &mut TestGame| {
+        let world = game.app.world_mut();
+        world
+            .query_filtered::<(), (With<Proton>, With<TextureAtlas>, With<SpriteAnimation>)>()
+            .iter(world)
+            .count()
+    };
+    assert!(game.protons() > 0);
+    assert_eq!(animated(&mut game), game.protons());
+
+    // The solid variant draws protons from a plain image rather than the sprite sheet.
+    set_variant(&mut game, Some("solid"));
+    assert_eq!(animated(&mut game), 0);
+    let (state, _) = sprite::<Electron>(&mut game);
+    assert_eq!(state, AnimationState::Idle);
+
+    set_variant(&mut game, None);
+    assert_eq!(animated(&mut game), game.protons());
+}
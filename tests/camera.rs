@@ -0,0 +1,62 @@
+//! Framing the atom, and zooming and panning around it.
+
+mod common;
+
+use atomiccycles::game::camera::{CameraView, Framing};
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+use common::TestGame;
+
+const VIEWPORT: Vec2 = Vec2::new(1280., 720.);
+
+#[test]
+fn small_atoms_sit_between_the_panels() {
+    let framing = Framing::fit(VIEWPORT, 216.);
+    assert_eq!(framing.scale, 1.);
+    // The atom is in the middle of the 615 pixels between the 400 pixel upgrades panel and the
+    // 265 pixel stats panel.
+    assert_eq!(framing.center, Vec2::new(-67.5, 0.));
+}
+
+#[test]
+fn large_atoms_are_zoomed_out_to_fit() {
+    let framing = Framing::fit(VIEWPORT, 500.);
+    assert!(framing.scale > 1.);
+    assert!(1000. / framing.scale <= VIEWPORT.y);
+    assert_eq!(framing.center.x, -67.5 * framing.scale);
+}
+
+#[test]
+fn narrow_windows_frame_the_whole_window() {
+    let framing = Framing::fit(Vec2::new(480., 800.), 216.);
+    assert_eq!(framing.center, Vec2::ZERO);
+    assert!(432. / framing.scale <= 480.);
+}
+
+fn scroll(game: &mut TestGame, lines: f32) {
+    game.app.world_mut().send_event(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.,
+        y: lines,
+        window: Entity::PLACEHOLDER,
+    });
+    game.update();
+}
+
+#[test]
+fn mouse_wheel_zooms_within_limits() {
+    let mut game = TestGame::new();
+    scroll(&mut game, 3.);
+    let zoom = game.app.world().resource::<CameraView>().zoom;
+    assert!(zoom > 1.);
+
+    for _ in 0..50 {
+        scroll(&mut game, -3.);
+    }
+    let zoomed_out = game.app.world().resource::<CameraView>().zoom;
+    assert!(zoomed_out < 1.);
+    scroll(&mut game, -3.);
+    assert_eq!(game.app.world().resource::<CameraView>().zoom, zoomed_out);
+}
@@ -0,0 +1,314 @@
+//! A headless copy of the game for testing its rules.
+//!
+//! [`TestGame`] runs the game with [`AppPlugin::headless`], so without a window, rendering or
+//! audio output, and starts a run straight away. Time only moves when the test advances it.
+
+#![allow(dead_code)]
+
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use atomiccycles::{
+    game::{
+        assets::AssetProgress,
+        cycles::CycleCount,
+        movement::Revolve,
+        replay::{Replay, ReplayPlayback, ReplayRecorder},
+        spawn::atom::{Electron, Neutron, Proton, Ring},
+        upgrades::{Upgrade, UpgradeButton},
+    },
+    screen::Screen,
+    AppPlugin,
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState,
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    ui::UiSystem,
+};
+
+/// An empty directory for one game's saved data, so tests don't see each other's saves or the
+/// player's own.
+pub fn fresh_data_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let name = format!(
+        "data-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    );
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// How far time moves on each update.
+pub const FRAME: Duration = Duration::from_millis(1000 / 60);
+
+/// The button to press on the next update.
+#[derive(Resource, Default)]
+struct PendingPress(Option<Entity>);
+
+/// Presses the pending button after Bevy has updated interactions, so it isn't overwritten.
+fn press_pending(
+    mut pending: ResMut<PendingPress>,
+    mut query_interaction: Query<&mut Interaction>,
+) {
+    if let Some(button) = pending.0.take() {
+        *query_interaction
+            .get_mut(button)
+            .expect("buttons have an interaction") = Interaction::Pressed;
+    }
+}
+
+pub struct TestGame {
+    pub app: App,
+    /// Updates run so far.
+    pub frames: u64,
+}
+
+impl TestGame {
+    /// Builds the game and starts a run.
+    pub fn new() -> Self {
+        let mut game = Self::launch();
+        game.wait_for_assets();
+        game.set_screen(Screen::Playing);
+        game
+    }
+
+    /// Builds the game and leaves it on the splash screen, as if it had just been opened.
+    pub fn launch() -> Self {
+        Self::launch_with(AppPlugin::headless())
+    }
+
+    /// Like [`TestGame::launch`], with `plugin` configured by the test.
+    /// Unless the test gives it a data directory, the game saves to a [`fresh_data_dir`].
+    pub fn launch_with(mut plugin: AppPlugin) -> Self {
+        if plugin.data_dir.is_none() {
+            plugin.data_dir = Some(fresh_data_dir());
+        }
+
+        let mut app = App::new();
+        app.add_plugins(plugin.with_seed(0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.init_resource::<PendingPress>();
+        app.add_systems(PreUpdate, press_pending.after(UiSystem::Focus));
+        // `App::run` would do this, but tests step the app themselves.
+        app.finish();
+        app.cleanup();
+
+        let mut game = Self { app, frames: 0 };
+        game.update();
+        game
+    }
+
+    /// Builds the game and starts a run that plays back `replay`.
+    pub fn replaying(replay: Replay) -> Self {
+        let mut game = Self::launch();
+        game.wait_for_assets();
+        game.app.insert_resource(ReplayPlayback::new(replay));
+        game.set_screen(Screen::Playing);
+        game
+    }
+
+    /// Goes to the loading screen and runs updates until every asset has loaded.
+    /// Panics if they don't load within a few seconds.
+    pub fn wait_for_assets(&mut self) {
+        self.set_screen(Screen::Loading);
+        for _ in 0..300 {
+            if self.app.world().resource::<AssetProgress>().is_done() {
+                return;
+            }
+            self.update();
+        }
+        panic!("assets didn't load");
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+        self.frames += 1;
+    }
+
+    /// Runs updates until `frames` updates have run in total.
+    pub fn advance_to_frame(&mut self, frames: u64) {
+        while self.frames < frames {
+            self.update();
+        }
+    }
+
+    /// Presses and releases `key`, as if typed on a keyboard.
+    pub fn tap(&mut self, key: KeyCode) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world_mut().send_event(KeyboardInput {
+                key_code: key,
+                logical_key: Key::Unidentified(NativeKey::Unidentified),
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            self.update();
+        }
+    }
+
+    /// Everything the player has done so far this run.
+    pub fn recording(&self) -> Replay {
+        self.app
+            .world()
+            .resource::<ReplayRecorder>()
+            .replay()
+            .clone()
+    }
+
+    pub fn set_screen(&mut self, screen: Screen) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(screen);
+        // One update to change screen, and one for the new screen to spawn its contents.
+        self.update();
+        self.update();
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.app.world().resource::<State<Screen>>().get().clone()
+    }
+
+    /// Runs the game for at least `duration`, one frame at a time.
+    pub fn advance(&mut self, duration: Duration) {
+        let frames = duration.as_nanos().div_ceil(FRAME.as_nanos());
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    /// Clicks the button that buys `upgrade`, for the ring at `ring` if it is a ring upgrade.
+    /// Panics if there is no such button.
+    pub fn press(&mut self, upgrade: impl Upgrade, ring: Option<usize>) {
+        let ring = ring.map(|index| self.ring(index));
+        let button = self
+            .app
+            .world_mut()
+            .query::<(Entity, &UpgradeButton)>()
+            .iter(self.app.world())
+            .find(|(_, button)| button.upgrade == upgrade.id() && button.ring == ring)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| panic!("no button for {:?} on ring {ring:?}", upgrade.id()));
+
+        self.click(button);
+    }
+
+    /// The button labelled `text`. Panics if there is no such button.
+    pub fn button(&mut self, text: &str) -> Entity {
+        let world = self.app.world_mut();
+        let buttons = world
+            .query_filtered::<(Entity, &Children), With<Button>>()
+            .iter(world)
+            .map(|(entity, children)| (entity, children.to_vec()))
+            .collect::<Vec<_>>();
+        buttons
+            .into_iter()
+            .find(|(_, children)| {
+                children.iter().any(|child| {
+                    world
+                        .get::<Text>(*child)
+                        .is_some_and(|label| label.sections[0].value == text)
+                })
+            })
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| panic!("no {text:?} button"))
+    }
+
+    /// Clicks `button`.
+    pub fn click(&mut self, button: Entity) {
+        self.app.world_mut().resource_mut::<PendingPress>().0 = Some(button);
+        self.update();
+        // Bevy lets go of the button on the next update, since there's no cursor over it.
+        self.update();
+    }
+
+    /// Every piece of text on screen.
+    pub fn texts(&mut self) -> Vec<String> {
+        self.app
+            .world_mut()
+            .query::<&Text>()
+            .iter(self.app.world())
+            .flat_map(|text| text.sections.iter().map(|section| section.value.clone()))
+            .collect()
+    }
+
+    pub fn cycles(&self) -> u32 {
+        self.app.world().resource::<CycleCount>().0
+    }
+
+    pub fn set_cycles(&mut self, cycles: u32) {
+        self.app.world_mut().resource_mut::<CycleCount>().0 = cycles;
+    }
+
+    /// The ring entity at `index`, counting from zero. Panics if it hasn't been bought.
+    pub fn ring(&mut self, index: usize) -> Entity {
+        self.app
+            .world_mut()
+            .query::<(Entity, &Ring)>()
+            .iter(self.app.world())
+            .find(|(_, ring)| ring.index == index)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| panic!("ring {index} hasn't been bought"))
+    }
+
+    pub fn count<T: Component>(&mut self) -> usize {
+        self.app
+            .world_mut()
+            .query_filtered::<(), With<T>>()
+            .iter(self.app.world())
+            .count()
+    }
+
+    pub fn rings(&mut self) -> usize {
+        self.count::<Ring>()
+    }
+
+    pub fn protons(&mut self) -> usize {
+        self.count::<Proton>()
+    }
+
+    pub fn neutrons(&mut self) -> usize {
+        self.count::<Neutron>()
+    }
+
+    /// The number of electrons on the ring at `index`.
+    pub fn electrons(&mut self, index: usize) -> usize {
+        let ring = self.ring(index);
+        let world = self.app.world();
+        world
+            .get::<Children>(ring)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| world.get::<Electron>(**child).is_some())
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn revolve(&mut self, index: usize) -> &Revolve {
+        let ring = self.ring(index);
+        self.app
+            .world()
+            .get::<Revolve>(ring)
+            .expect("rings revolve")
+    }
+
+    pub fn cycle_time(&mut self, index: usize) -> Option<Duration> {
+        let ring = self.ring(index);
+        self.app
+            .world()
+            .get::<Ring>(ring)
+            .and_then(|ring| ring.cycle_timer.as_ref())
+            .map(Timer::duration)
+    }
+}
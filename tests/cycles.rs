@@ -0,0 +1,39 @@
+//! Earning cycles from revolving electrons.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::game::{
+    cycles::CycleSource,
+    movement::RevolutionController,
+    spawn::atom::Electron,
+    stats::CycleStats,
+    upgrades::{BuyElectron, BuyNextRing},
+};
+use bevy::prelude::*;
+use common::TestGame;
+
+#[test]
+fn queued_revolutions_keep_their_sources() {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+
+    let world = game.app.world_mut();
+    let mut controller = world
+        .query_filtered::<&mut RevolutionController, With<Electron>>()
+        .single_mut(world);
+    controller.count_max = 2;
+    controller.add_count(CycleSource::Automatic);
+    controller.add_count(CycleSource::Manual);
+
+    game.advance(Duration::from_secs(30));
+    let stats = game.app.world().resource::<CycleStats>();
+    let window = 60.;
+    assert!(stats.source_rate(window, CycleSource::Automatic) > 0.);
+    assert_eq!(
+        stats.source_rate(window, CycleSource::Automatic),
+        stats.source_rate(window, CycleSource::Manual)
+    );
+}
@@ -0,0 +1,27 @@
+//! Running the game from assets built into the executable, with the `embedded_assets` feature.
+
+#![cfg(feature = "embedded_assets")]
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::{
+    game::assets::{AssetManifest, ManifestHandle},
+    screen::Screen,
+};
+use bevy::{asset::io::AssetSourceId, prelude::*};
+use common::TestGame;
+
+#[test]
+fn manifest_loads_from_the_executable() {
+    let mut game = TestGame::launch();
+    game.advance(Duration::from_secs(5));
+    assert_eq!(game.screen(), Screen::Title);
+
+    let world = game.app.world();
+    let manifest = &world.resource::<ManifestHandle>().0;
+    let path = world.resource::<AssetServer>().get_path(manifest).unwrap();
+    assert_eq!(path.source(), &AssetSourceId::from("embedded"));
+    assert!(world.resource::<Assets<AssetManifest>>().contains(manifest));
+}
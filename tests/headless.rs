@@ -0,0 +1,18 @@
+//! Running the whole game without a window, renderer or audio device.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::screen::Screen;
+use common::TestGame;
+
+#[test]
+fn headless_game_reaches_the_title_screen() {
+    let mut game = TestGame::launch();
+    assert_eq!(game.screen(), Screen::Splash);
+
+    // Past the splash screen, and loading the stand-in assets.
+    game.advance(Duration::from_secs(5));
+    assert_eq!(game.screen(), Screen::Title);
+}
@@ -0,0 +1,49 @@
+//! Loading assets, and recovering when they don't load.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::{
+    game::assets::{AssetProgress, HandleMap, ImageKey},
+    screen::Screen,
+};
+use bevy::prelude::*;
+use common::TestGame;
+
+const MISSING_IMAGE: &str = "images/missing.png";
+
+#[test]
+fn loading_counts_every_asset() {
+    let mut game = TestGame::launch();
+    game.advance(Duration::from_secs(5));
+    assert_eq!(game.screen(), Screen::Title);
+
+    let progress = game.app.world().resource::<AssetProgress>().total();
+    assert!(progress.total > 0);
+    assert!(progress.is_done());
+    assert!(progress.failed.is_empty());
+}
+
+#[test]
+fn failed_asset_can_be_retried() {
+    let mut game = TestGame::launch();
+    let world = game.app.world_mut();
+    let missing = world.resource::<AssetServer>().load(MISSING_IMAGE);
+    world
+        .resource_mut::<HandleMap<ImageKey>>()
+        .insert(ImageKey::Electron, missing);
+
+    game.advance(Duration::from_secs(5));
+    assert_eq!(game.screen(), Screen::LoadingFailed);
+    let progress = game.app.world().resource::<AssetProgress>().total();
+    assert_eq!(progress.failed, [MISSING_IMAGE]);
+    assert!(game.texts().iter().any(|text| text.contains(MISSING_IMAGE)));
+
+    // The image is still missing, so retrying fails again.
+    let retry = game.button("Retry");
+    game.click(retry);
+    assert_eq!(game.screen(), Screen::Loading);
+    game.advance(Duration::from_secs(1));
+    assert_eq!(game.screen(), Screen::LoadingFailed);
+}
@@ -0,0 +1,121 @@
+//! The asset manifest, which lists where every asset is loaded from.
+
+use atomiccycles::game::assets::{
+    AssetManifest, ImageKey, ManifestError, SfxKey, SoundtrackKey, MANIFEST_PATH,
+};
+
+fn shipped_manifest() -> AssetManifest {
+    let path = format!("{}/assets/{MANIFEST_PATH}", env!("CARGO_MANIFEST_DIR"));
+    let text = std::fs::read_to_string(path).expect("the manifest is readable");
+    AssetManifest::parse(&text).expect("the manifest parses")
+}
+
+#[test]
+fn shipped_manifest_lists_every_asset() {
+    let manifest = shipped_manifest();
+    manifest.validate::<ImageKey>().unwrap();
+    manifest.validate::<SfxKey>().unwrap();
+    manifest.validate::<SoundtrackKey>().unwrap();
+}
+
+#[test]
+fn variant_replaces_only_its_own_entries() {
+    let manifest = shipped_manifest();
+    let (variant, _) = manifest.variants().next().expect("a variant is shipped");
+
+    let base = manifest.entry(ImageKey::Proton, None).unwrap();
+    let replaced = manifest.entry(ImageKey::Proton, Some(variant)).unwrap();
+    assert_ne!(base, replaced);
+    assert_eq!(
+        manifest.entry(ImageKey::Electron, None),
+        manifest.entry(ImageKey::Electron, Some(variant)),
+    );
+    assert_eq!(
+        manifest.entry(ImageKey::Proton, Some("unknown")),
+        Some(base)
+    );
+}
+
+#[test]
+fn missing_entry_is_rejected() {
+    let manifest = AssetManifest::parse(
+        r#"(
+            assets: {
+                "soundtracks": {
+                    "Credits": (path: "credits.ogg"),
+                },
+            },
+        )"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        manifest.validate::<SoundtrackKey>(),
+        Err(ManifestError::MissingEntry { key, .. }) if key == "Gameplay"
+    ));
+}
+
+#[test]
+fn unknown_variant_key_is_rejected() {
+    let manifest = AssetManifest::parse(
+        r#"(
+            assets: {
+                "soundtracks": {
+                    "Credits": (path: "credits.ogg"),
+                    "Gameplay": (path: "gameplay.ogg"),
+                },
+            },
+            variants: {
+                "quiet": (
+                    name: "Quiet",
+                    assets: {
+                        "soundtracks": {
+                            "Title": (path: "title.ogg"),
+                        },
+                    },
+                ),
+            },
+        )"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        manifest.validate::<SoundtrackKey>(),
+        Err(ManifestError::UnknownKey { key, .. }) if key == "Title"
+    ));
+}
+
+#[test]
+fn malformed_manifest_is_rejected() {
+    assert!(matches!(
+        AssetManifest::parse("(assets: {"),
+        Err(ManifestError::Parse(_))
+    ));
+}
+
+#[test]
+fn clip_past_the_atlas_is_rejected() {
+    let manifest = AssetManifest::parse(
+        r#"#![enable(implicit_some)]
+        (
+            assets: {
+                "images": {
+                    "Electron": (
+                        path: "particles.png",
+                        atlas: (
+                            tile_size: 32,
+                            columns: 4,
+                            rows: 1,
+                            clips: { Idle: (first: 2, frames: 4, fps: 8.0) },
+                        ),
+                    ),
+                    "Proton": (path: "proton.png"),
+                    "Neutron": (path: "neutron.png"),
+                },
+            },
+        )"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        manifest.validate::<ImageKey>(),
+        Err(ManifestError::InvalidAtlas { key, .. }) if key == "Electron"
+    ));
+}
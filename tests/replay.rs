@@ -0,0 +1,204 @@
+//! Recording runs and playing them back.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::game::{
+    replay::{Replay, ReplayAction, ReplayEvent},
+    upgrades::{
+        BuyElectron, BuyNextRing, CycleUpgrade, PurchaseMultiplier, SpeedUpgrade, MAX_RINGS,
+    },
+};
+use bevy::prelude::*;
+use common::TestGame;
+
+/// Cycles to start runs with, enough to buy every upgrade at least once.
+const BUDGET: u32 = 1000;
+
+/// Plays a short run, buying one of each upgrade.
+fn play_run() -> TestGame {
+    let mut game = TestGame::new();
+    game.set_cycles(BUDGET);
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+    game.tap(KeyCode::Space);
+    game.advance(Duration::from_secs(2));
+    game.press(CycleUpgrade, Some(0));
+    game.press(SpeedUpgrade, Some(0));
+    game.advance(Duration::from_secs(5));
+    game.tap(KeyCode::Space);
+    game.advance(Duration::from_secs(5));
+    game
+}
+
+#[test]
+fn replay_text_round_trips() {
+    let replay = Replay {
+        seed: 42,
+        events: vec![
+            ReplayEvent {
+                time: Duration::from_millis(500),
+                action: ReplayAction::Purchase {
+                    upgrade: "buy_next_ring".to_string(),
+                    ring: None,
+                    multiplier: PurchaseMultiplier::One,
+                },
+            },
+            ReplayEvent {
+                time: Duration::from_nanos(1_016_666_667),
+                action: ReplayAction::ManualCycle,
+            },
+            ReplayEvent {
+                time: Duration::from_secs(2),
+                action: ReplayAction::ClickElectron {
+                    ring: 0,
+                    electron: 1,
+                },
+            },
+            ReplayEvent {
+                time: Duration::from_secs(3),
+                action: ReplayAction::Purchase {
+                    upgrade: "speed".to_string(),
+                    ring: Some(2),
+                    multiplier: PurchaseMultiplier::Max,
+                },
+            },
+            ReplayEvent {
+                time: Duration::from_secs(4),
+                action: ReplayAction::SetTimeScale(2.5),
+            },
+            ReplayEvent {
+                time: Duration::from_secs(5),
+                action: ReplayAction::SetPaused(true),
+            },
+            ReplayEvent {
+                time: Duration::from_secs(6),
+                action: ReplayAction::SetPaused(false),
+            },
+        ],
+    };
+    assert_eq!(Replay::parse(&replay.to_string()), Ok(replay));
+}
+
+#[test]
+fn invalid_replay_is_rejected() {
+    assert!(Replay::parse("0.500000000 cycle\n").is_err());
+    assert!(Replay::parse("seed 1\n0.500000000 dance\n").is_err());
+    assert!(Replay::parse("seed 1\n0.5 cycle\n").is_err());
+}
+
+#[test]
+fn run_is_recorded() {
+    let game = play_run();
+    let replay = game.recording();
+    let actions: Vec<_> = replay.events.iter().map(|event| &event.action).collect();
+
+    let purchases = actions
+        .iter()
+        .filter(|action| matches!(action, ReplayAction::Purchase { .. }))
+        .count();
+    let cycles = actions
+        .iter()
+        .filter(|action| matches!(action, ReplayAction::ManualCycle))
+        .count();
+    assert_eq!(purchases, 4);
+    assert_eq!(cycles, 2);
+    assert_eq!(
+        actions[0],
+        &ReplayAction::Purchase {
+            upgrade: "buy_next_ring".to_string(),
+            ring: None,
+            multiplier: PurchaseMultiplier::One,
+        }
+    );
+    assert!(replay
+        .events
+        .windows(2)
+        .all(|events| events[0].time <= events[1].time));
+}
+
+#[test]
+fn playback_reproduces_the_run() {
+    let mut recorded = play_run();
+    let replay = Replay::parse(&recorded.recording().to_string()).unwrap();
+
+    let mut played = TestGame::replaying(replay);
+    played.set_cycles(BUDGET);
+    played.advance_to_frame(recorded.frames);
+
+    assert_eq!(played.cycles(), recorded.cycles());
+    assert_eq!(played.rings(), recorded.rings());
+    assert_eq!(played.electrons(0), recorded.electrons(0));
+    assert_eq!(played.revolve(0).level, recorded.revolve(0).level);
+    assert_eq!(played.cycle_time(0), recorded.cycle_time(0));
+}
+
+fn purchase(millis: u64, upgrade: &str, ring: Option<usize>) -> ReplayEvent {
+    ReplayEvent {
+        time: Duration::from_millis(millis),
+        action: ReplayAction::Purchase {
+            upgrade: upgrade.to_string(),
+            ring,
+            multiplier: PurchaseMultiplier::One,
+        },
+    }
+}
+
+#[test]
+fn replayed_purchase_fails_when_unaffordable() {
+    let replay = Replay {
+        seed: 0,
+        events: vec![
+            purchase(100, "buy_next_ring", None),
+            purchase(150, "buy_electron", Some(0)),
+            purchase(200, "speed", Some(0)),
+        ],
+    };
+
+    // The starting cycles only cover the first ring and electron.
+    let mut game = TestGame::replaying(replay);
+    game.advance(Duration::from_millis(300));
+    assert_eq!(game.rings(), 1);
+    assert_eq!(game.electrons(0), 1);
+    assert_eq!(game.revolve(0).level, 0);
+}
+
+#[test]
+fn playback_follows_game_time() {
+    let replay = Replay {
+        seed: 0,
+        events: vec![purchase(1000, "buy_next_ring", None)],
+    };
+    let mut game = TestGame::replaying(replay);
+    game.app
+        .world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(2.);
+    // A second of game time passes in about half a second.
+    game.advance(Duration::from_millis(600));
+    assert_eq!(game.rings(), 1);
+}
+
+#[test]
+fn playback_keeps_the_players_multiplier() {
+    let mut event = purchase(100, "buy_next_ring", None);
+    if let ReplayAction::Purchase { multiplier, .. } = &mut event.action {
+        *multiplier = PurchaseMultiplier::Max;
+    }
+    let replay = Replay {
+        seed: 0,
+        events: vec![event],
+    };
+    let mut game = TestGame::replaying(replay);
+    game.app
+        .world_mut()
+        .insert_resource(PurchaseMultiplier::Ten);
+    game.set_cycles(u32::MAX / 2);
+    game.advance(Duration::from_millis(200));
+    assert_eq!(game.rings(), MAX_RINGS);
+    assert_eq!(
+        *game.app.world().resource::<PurchaseMultiplier>(),
+        PurchaseMultiplier::Ten
+    );
+}
@@ -0,0 +1,98 @@
+//! Motion trails behind revolving electrons.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::{
+    game::{
+        trails::{ElectronTrail, TrailMesh},
+        upgrades::{BuyElectron, BuyNextRing, SpeedUpgrade},
+    },
+    screen::Screen,
+    settings::Settings,
+};
+use bevy::prelude::*;
+use common::TestGame;
+
+/// A run with an electron on the first ring, and trails turned on or off.
+fn started_game(trails: bool) -> TestGame {
+    let mut game = TestGame::new();
+    game.app
+        .world_mut()
+        .resource_mut::<Settings>()
+        .electron_trails = trails;
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+    game
+}
+
+fn trail_length(game: &mut TestGame) -> f32 {
+    let world = game.app.world_mut();
+    world
+        .query::<&ElectronTrail>()
+        .iter(world)
+        .map(ElectronTrail::length)
+        .sum()
+}
+
+fn trail_mesh_visible(game: &mut TestGame) -> bool {
+    let world = game.app.world_mut();
+    world
+        .query_filtered::<&Visibility, With<TrailMesh>>()
+        .single(world)
+        != Visibility::Hidden
+}
+
+#[test]
+fn revolving_electrons_leave_a_trail() {
+    let mut game = started_game(true);
+    assert_eq!(trail_length(&mut game), 0.);
+    assert!(!trail_mesh_visible(&mut game));
+
+    game.tap(KeyCode::Space);
+    game.advance(Duration::from_millis(300));
+    assert!(trail_length(&mut game) > 0.);
+    assert!(trail_mesh_visible(&mut game));
+
+    // The trail catches up once the electron stops.
+    game.advance(Duration::from_secs(5));
+    assert_eq!(trail_length(&mut game), 0.);
+    assert!(!trail_mesh_visible(&mut game));
+}
+
+#[test]
+fn faster_electrons_leave_longer_trails() {
+    let mut slow = started_game(true);
+    slow.tap(KeyCode::Space);
+    slow.advance(Duration::from_millis(300));
+
+    let mut fast = started_game(true);
+    fast.set_cycles(10_000);
+    for _ in 0..5 {
+        fast.press(SpeedUpgrade, Some(0));
+    }
+    fast.tap(KeyCode::Space);
+    fast.advance(Duration::from_millis(300));
+
+    assert!(trail_length(&mut fast) > 2. * trail_length(&mut slow));
+}
+
+#[test]
+fn trails_can_be_turned_off() {
+    let mut game = started_game(false);
+    game.tap(KeyCode::Space);
+    game.advance(Duration::from_millis(300));
+    assert_eq!(trail_length(&mut game), 0.);
+    assert!(!trail_mesh_visible(&mut game));
+}
+
+#[test]
+fn settings_screen_toggles_trails() {
+    let mut game = started_game(true);
+    game.set_screen(Screen::Settings);
+    let button = game.button("Trails: On");
+    game.click(button);
+    assert!(!game.app.world().resource::<Settings>().electron_trails);
+    assert!(game.texts().contains(&"Trails: Off".to_string()));
+}
@@ -0,0 +1,261 @@
+//! Buying each upgrade through its button.
+
+mod common;
+
+use std::time::Duration;
+
+use atomiccycles::{
+    game::upgrades::{
+        costs::{
+            compute_bulk_cost, compute_electron_cost, compute_ring_cost, compute_speed_cost,
+            BulkCost, STARTING_CYCLES,
+        },
+        transactions::TransactionLog,
+        BuyElectron, BuyNextRing, CycleUpgrade, PurchaseMultiplier, SpeedUpgrade, MAX_RINGS,
+        SPEED_UPGRADE_INCREMENT,
+    },
+    screen::Screen,
+};
+use common::TestGame;
+
+fn set_multiplier(game: &mut TestGame, multiplier: PurchaseMultiplier) {
+    game.app.world_mut().insert_resource(multiplier);
+}
+
+/// The summed cost of speed levels `levels` on the first ring.
+fn speed_cost(levels: std::ops::Range<u32>) -> u32 {
+    levels.map(|level| compute_speed_cost(0, level)).sum()
+}
+
+/// A run with the first ring bought and one electron on it.
+fn started_game() -> TestGame {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    game.press(BuyElectron, Some(0));
+    game
+}
+
+#[test]
+fn run_starts_empty() {
+    let mut game = TestGame::new();
+    assert_eq!(game.cycles(), STARTING_CYCLES);
+    assert_eq!(game.rings(), 0);
+    assert_eq!(game.protons(), 0);
+    assert_eq!(game.neutrons(), 0);
+}
+
+#[test]
+fn buying_a_ring_adds_a_ring() {
+    let mut game = TestGame::new();
+    game.press(BuyNextRing, None);
+    assert_eq!(game.rings(), 1);
+    assert_eq!(game.cycles(), STARTING_CYCLES - compute_ring_cost(0));
+}
+
+#[test]
+fn buying_electrons_adds_nucleons() {
+    let mut game = started_game();
+    assert_eq!(game.electrons(0), 1);
+    // The first electron comes with a lone proton.
+    assert_eq!(game.protons(), 1);
+    assert_eq!(game.neutrons(), 0);
+
+    game.set_cycles(compute_electron_cost(0, 1));
+    game.press(BuyElectron, Some(0));
+    assert_eq!(game.electrons(0), 2);
+    assert_eq!(game.protons(), 2);
+    assert_eq!(game.neutrons(), 1);
+    assert_eq!(game.cycles(), 0);
+}
+
+#[test]
+fn full_ring_takes_no_more_electrons() {
+    let mut game = started_game();
+    game.set_cycles(1000);
+    game.press(BuyElectron, Some(0));
+    game.press(BuyElectron, Some(0));
+    assert_eq!(game.electrons(0), 2);
+    assert_eq!(game.cycles(), 1000 - compute_electron_cost(0, 1));
+}
+
+#[test]
+fn unaffordable_purchase_changes_nothing() {
+    let mut game = started_game();
+    game.set_cycles(0);
+    game.press(BuyElectron, Some(0));
+    game.press(SpeedUpgrade, Some(0));
+    assert_eq!(game.electrons(0), 1);
+    assert_eq!(game.revolve(0).level, 0);
+    assert_eq!(game.cycles(), 0);
+}
+
+#[test]
+fn speed_upgrade_speeds_up_the_ring() {
+    let mut game = started_game();
+    let speed = game.revolve(0).speed;
+    game.set_cycles(compute_speed_cost(0, 0));
+    game.press(SpeedUpgrade, Some(0));
+    assert_eq!(game.revolve(0).level, 1);
+    assert_eq!(game.revolve(0).speed, speed + SPEED_UPGRADE_INCREMENT);
+    assert_eq!(game.cycles(), 0);
+}
+
+#[test]
+fn cycle_upgrade_earns_cycles_over_time() {
+    let mut game = started_game();
+    game.set_cycles(1000);
+    game.press(CycleUpgrade, Some(0));
+    let cycle_time = game.cycle_time(0).expect("the ring has a cycle timer");
+
+    let cycles = game.cycles();
+    game.advance(cycle_time * 3 + Duration::from_secs(1));
+    assert!(
+        game.cycles() > cycles,
+        "expected more than {cycles} cycles, got {}",
+        game.cycles()
+    );
+}
+
+#[test]
+fn cycle_upgrade_shortens_the_timer() {
+    let mut game = started_game();
+    game.set_cycles(1000);
+    game.press(CycleUpgrade, Some(0));
+    let first = game.cycle_time(0).unwrap();
+    game.press(CycleUpgrade, Some(0));
+    assert!(game.cycle_time(0).unwrap() < first);
+}
+
+#[test]
+fn rings_stop_at_max_rings() {
+    let mut game = TestGame::new();
+    game.set_cycles(u32::MAX / 2);
+    for _ in 0..MAX_RINGS {
+        game.press(BuyNextRing, None);
+    }
+    assert_eq!(game.rings(), MAX_RINGS);
+
+    let cycles = game.cycles();
+    game.press(BuyNextRing, None);
+    assert_eq!(game.rings(), MAX_RINGS);
+    assert_eq!(game.cycles(), cycles);
+}
+
+#[test]
+fn bulk_cost_sums_each_multiplier() {
+    let costs = 1..;
+    for (multiplier, levels) in [
+        (PurchaseMultiplier::One, 1),
+        (PurchaseMultiplier::Ten, 10),
+        (PurchaseMultiplier::Hundred, 100),
+    ] {
+        assert_eq!(
+            compute_bulk_cost(costs.clone(), multiplier, 0),
+            Some(BulkCost {
+                levels,
+                cost: levels * (levels + 1) / 2,
+            })
+        );
+    }
+    // Only as many levels as are left.
+    assert_eq!(
+        compute_bulk_cost(costs.clone().take(3), PurchaseMultiplier::Ten, 0),
+        Some(BulkCost { levels: 3, cost: 6 })
+    );
+    assert_eq!(
+        compute_bulk_cost(costs.take(0), PurchaseMultiplier::Ten, 0),
+        None
+    );
+}
+
+#[test]
+fn bulk_cost_max_buys_what_the_budget_allows() {
+    // 1 + 2 + 3 + 4 is exactly 10.
+    assert_eq!(
+        compute_bulk_cost(1.., PurchaseMultiplier::Max, 10),
+        Some(BulkCost {
+            levels: 4,
+            cost: 10
+        })
+    );
+    assert_eq!(
+        compute_bulk_cost(1.., PurchaseMultiplier::Max, 9),
+        Some(BulkCost { levels: 3, cost: 6 })
+    );
+    // Not even one level is affordable, so one level is quoted.
+    assert_eq!(
+        compute_bulk_cost(5.., PurchaseMultiplier::Max, 4),
+        Some(BulkCost { levels: 1, cost: 5 })
+    );
+}
+
+#[test]
+fn multiplier_buys_several_levels_at_once() {
+    let mut game = started_game();
+    set_multiplier(&mut game, PurchaseMultiplier::Ten);
+    let speed = game.revolve(0).speed;
+    game.set_cycles(speed_cost(0..10));
+    game.press(SpeedUpgrade, Some(0));
+    assert_eq!(game.revolve(0).level, 10);
+    assert_eq!(game.revolve(0).speed, speed + SPEED_UPGRADE_INCREMENT * 10.);
+    assert_eq!(game.cycles(), 0);
+}
+
+#[test]
+fn multiplier_needs_every_level_to_be_affordable() {
+    let mut game = started_game();
+    set_multiplier(&mut game, PurchaseMultiplier::Ten);
+    game.set_cycles(speed_cost(0..10) - 1);
+    game.press(SpeedUpgrade, Some(0));
+    assert_eq!(game.revolve(0).level, 0);
+    assert_eq!(game.cycles(), speed_cost(0..10) - 1);
+}
+
+#[test]
+fn max_multiplier_spends_an_exact_budget() {
+    let mut game = started_game();
+    set_multiplier(&mut game, PurchaseMultiplier::Max);
+    game.set_cycles(speed_cost(0..3));
+    game.press(SpeedUpgrade, Some(0));
+    assert_eq!(game.revolve(0).level, 3);
+    assert_eq!(game.cycles(), 0);
+}
+
+#[test]
+fn multiplier_stops_at_ring_capacity() {
+    let mut game = started_game();
+    set_multiplier(&mut game, PurchaseMultiplier::Hundred);
+    game.set_cycles(1000);
+    game.press(BuyElectron, Some(0));
+    // The first ring holds two electrons.
+    assert_eq!(game.electrons(0), 2);
+    assert_eq!(game.cycles(), 1000 - compute_electron_cost(0, 1));
+}
+
+#[test]
+fn max_multiplier_stops_at_max_rings() {
+    let mut game = TestGame::new();
+    set_multiplier(&mut game, PurchaseMultiplier::Max);
+    game.set_cycles(u32::MAX / 2);
+    game.press(BuyNextRing, None);
+    assert_eq!(game.rings(), MAX_RINGS);
+
+    let spent: u32 = (0..MAX_RINGS).map(compute_ring_cost).sum();
+    assert_eq!(game.cycles(), u32::MAX / 2 - spent);
+}
+
+#[test]
+fn transactions_are_timed_from_the_start_of_the_run() {
+    let mut game = TestGame::launch();
+    // Time spent before the run isn't counted.
+    game.advance(Duration::from_secs(5));
+    game.wait_for_assets();
+    game.set_screen(Screen::Playing);
+    game.advance(Duration::from_secs(1));
+    game.press(BuyNextRing, None);
+
+    let log = game.app.world().resource::<TransactionLog>();
+    let time = log.iter().last().expect("the purchase was logged").time;
+    assert!(time >= Duration::from_secs(1));
+    assert!(time < Duration::from_secs(2));
+}
@@ -0,0 +1,154 @@
+//! Compares rendered frames against the golden images in `tests/golden`.
+//!
+//! Rendering needs a GPU backend, but a software one like llvmpipe is enough. Without any,
+//! the test fails rather than passing without having compared anything. Run with
+//! `UPDATE_GOLDENS=1` to replace the golden images with the current frames after changing how
+//! the game looks on purpose.
+
+mod common;
+
+use std::{path::PathBuf, time::Duration};
+
+use atomiccycles::{
+    game::{
+        ring_material::RingMaterial,
+        spawn::atom::Ring,
+        upgrades::{BuyElectron, BuyNextRing},
+    },
+    offscreen::OffscreenTarget,
+    screen::Screen,
+    AppPlugin,
+};
+use bevy::prelude::*;
+use common::{fresh_data_dir, TestGame};
+use image::{imageops, Rgba, RgbaImage};
+
+const SIZE: UVec2 = UVec2::new(1280, 720);
+/// How far apart a pixel's channels may be before the pixel counts as different.
+const CHANNEL_TOLERANCE: u8 = 8;
+/// The share of pixels that may differ, for antialiasing that varies between renderers.
+const MAX_DIFFERENT_PIXELS: f64 = 0.005;
+
+#[test]
+fn frames_match_golden_images() {
+    // Start from the same saved data every time, with the tutorial out of the way.
+    let data_dir = fresh_data_dir();
+    std::fs::write(data_dir.join("settings.txt"), "tutorial_complete=true\n").unwrap();
+    let plugin = AppPlugin::offscreen(SIZE).with_data_dir(data_dir);
+    let mut game = TestGame::launch_with(plugin);
+
+    game.advance(Duration::from_secs(5));
+    assert_eq!(game.screen(), Screen::Title);
+    assert_matches_golden("title", &capture(&mut game));
+
+    game.set_screen(Screen::Playing);
+    game.set_cycles(10_000);
+    for ring in 0..3 {
+        game.press(BuyNextRing, None);
+        game.press(BuyElectron, Some(ring));
+        game.press(BuyElectron, Some(ring));
+    }
+    // Wait for the achievement toasts to go away.
+    game.advance(Duration::from_secs(6));
+    let frame = capture(&mut game);
+    let plain_rings = game
+        .app
+        .world_mut()
+        .query_filtered::<(), (With<Ring>, Without<Handle<RingMaterial>>)>()
+        .iter(game.app.world())
+        .count();
+    assert_eq!(plain_rings, 0, "rings are drawn by the ring shader");
+    assert_matches_golden("atom_three_rings", &frame);
+    assert_matches_golden(
+        "upgrades_panel",
+        &crop_to_node(&mut game, &frame, "Upgrades Panel"),
+    );
+}
+
+/// Renders a frame and reads it back.
+fn capture(game: &mut TestGame) -> RgbaImage {
+    game.app
+        .world_mut()
+        .resource_mut::<OffscreenTarget>()
+        .request_capture();
+    game.update();
+    let frame = game
+        .app
+        .world()
+        .resource::<OffscreenTarget>()
+        .take_capture()
+        .expect("the frame was captured");
+    RgbaImage::from_raw(frame.width(), frame.height(), frame.data).expect("frames are RGBA")
+}
+
+/// The part of `frame` covered by the UI node called `name`.
+fn crop_to_node(game: &mut TestGame, frame: &RgbaImage, name: &str) -> RgbaImage {
+    let (node, transform) = game
+        .app
+        .world_mut()
+        .query::<(&Name, &Node, &GlobalTransform)>()
+        .iter(game.app.world())
+        .find(|(node_name, ..)| node_name.as_str() == name)
+        .map(|(_, node, transform)| (node.size(), transform.translation().truncate()))
+        .unwrap_or_else(|| panic!("no UI node called {name}"));
+    let corner = (transform - node / 2.).max(Vec2::ZERO).as_uvec2();
+    let size = node.as_uvec2();
+    imageops::crop_imm(frame, corner.x, corner.y, size.x, size.y).to_image()
+}
+
+fn assert_matches_golden(name: &str, frame: &RgbaImage) {
+    let golden_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        std::fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+        frame.save(&golden_path).unwrap();
+        return;
+    }
+    let golden = image::open(&golden_path)
+        .unwrap_or_else(|error| {
+            panic!(
+                "cannot open {}: {error}, run with UPDATE_GOLDENS=1 to create it",
+                golden_path.display()
+            )
+        })
+        .to_rgba8();
+    assert_eq!(
+        golden.dimensions(),
+        frame.dimensions(),
+        "{name} is a different size to its golden image"
+    );
+
+    // Different pixels are shown in red, on a faded copy of the frame.
+    let mut diff = RgbaImage::new(frame.width(), frame.height());
+    let mut different = 0;
+    for ((expected, actual), diff) in golden.pixels().zip(frame.pixels()).zip(diff.pixels_mut()) {
+        let matches = expected
+            .0
+            .iter()
+            .zip(actual.0)
+            .all(|(expected, actual)| expected.abs_diff(actual) <= CHANNEL_TOLERANCE);
+        *diff = if matches {
+            Rgba(actual.0.map(|channel| channel / 4))
+        } else {
+            different += 1;
+            Rgba([255, 0, 0, 255])
+        };
+    }
+
+    let share = different as f64 / (frame.width() * frame.height()) as f64;
+    if share > MAX_DIFFERENT_PIXELS {
+        let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("visual-failures");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let actual_path = out_dir.join(format!("{name}.png"));
+        let diff_path = out_dir.join(format!("{name}.diff.png"));
+        frame.save(&actual_path).unwrap();
+        diff.save(&diff_path).unwrap();
+        panic!(
+            "{name} differs from its golden image in {:.2}% of pixels, see {} and {}",
+            share * 100.,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}